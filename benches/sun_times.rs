@@ -0,0 +1,58 @@
+use chrono::{Duration, NaiveDate};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+//a year of dates at Sheffield university's new computer science building
+fn sun_times(c: &mut Criterion) {
+    let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    let dates: Vec<_> = (0..365).map(|day| start + Duration::days(day)).collect();
+
+    let mut group = c.benchmark_group("sun_times");
+    group.throughput(Throughput::Elements(dates.len() as u64));
+    group.bench_function("sun_times", |b| {
+        b.iter(|| {
+            for &date in &dates {
+                black_box(sun_times::sun_times(black_box(date), 53.38, -1.48, 100.0));
+            }
+        })
+    });
+    group.bench_function("solar_events", |b| {
+        b.iter(|| {
+            for &date in &dates {
+                black_box(sun_times::solar_events(
+                    black_box(date),
+                    53.38,
+                    -1.48,
+                    100.0,
+                ));
+            }
+        })
+    });
+    group.finish();
+}
+
+//a 1° grid over the northern hemisphere's mid-latitudes, as a weather model might use
+fn grid(c: &mut Criterion) {
+    let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+    let points: Vec<_> = (0..60)
+        .flat_map(|latitude| {
+            (-180..180).map(move |longitude| (latitude as f64, longitude as f64, 0.0))
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("grid");
+    group.throughput(Throughput::Elements(points.len() as u64));
+    group.bench_function("sun_times", |b| {
+        b.iter(|| {
+            for &(latitude, longitude, elevation) in &points {
+                black_box(sun_times::sun_times(date, latitude, longitude, elevation));
+            }
+        })
+    });
+    group.bench_function("sun_times_grid", |b| {
+        b.iter(|| black_box(sun_times::sun_times_grid(black_box(date), &points)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, sun_times, grid);
+criterion_main!(benches);
@@ -1,31 +1,15 @@
-use chrono::{Datelike, Duration, Month, NaiveDate, TimeZone, Utc};
+use chrono::{Datelike, Month, TimeZone, Utc};
 use num_traits::FromPrimitive;
 
-/// uses the [sun_times::altitude] function to show a plot of sun up/sun down times
+/// uses the [sun_times::altitude_grid] function to show a plot of sun up/sun down times
 fn main() {
-    let date_range =
-        std::iter::successors(Some(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()), |date| {
-            let next = *date + Duration::days(1);
-            if next > NaiveDate::from_ymd_opt(2022, 12, 31).unwrap() {
-                None
-            } else {
-                Some(next)
-            }
-        });
+    let latitude = 80.0;
+    let grid = sun_times::altitude_grid(2022, latitude, 0.0, 24);
 
     let mut sun_up = [[false; 24]; 365];
-    for (x, date) in date_range.enumerate() {
-        for (y, hour) in (0..24).enumerate() {
-            let date = date
-                .and_hms_opt(hour, 0, 0)
-                .unwrap()
-                .and_local_timezone(Utc)
-                .single()
-                .unwrap();
-            let latitude = 80.0;
-            let altitude = sun_times::altitude(date, latitude, 0.0);
-
-            let is_visible = (-0.0..=90.0).contains(&altitude);
+    for (x, altitudes) in grid.iter().enumerate() {
+        for (y, altitude) in altitudes.iter().enumerate() {
+            let is_visible = (-0.0..=90.0).contains(altitude);
 
             sun_up[x][y] = is_visible
         }
@@ -0,0 +1,279 @@
+//! The constants of the sunrise equation for the body the sun is observed from, so that it can be used on other
+//! planets than the earth
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, Utc};
+#[cfg(feature = "chrono")]
+use num_traits::Float;
+
+#[cfg(feature = "chrono")]
+use crate::generic::{self, Crossing, Horizon, SunriseEquation};
+#[cfg(feature = "chrono")]
+use crate::{delta_t, JulianDate};
+
+/// The orbit and rotation of a body (e.g. a planet) from which the sun is observed, as used by the sunrise equation
+///
+/// The functions in the crate root all calculate for [CelestialBody::EARTH]. [sun_times_on] calculates for any body,
+/// e.g. [CelestialBody::MARS]. The values follow the form of those for the earth, see
+/// <https://en.wikipedia.org/wiki/Sunrise_equation>, so the times for other bodies are only approximate. For Mars they're
+/// within a minute of Mars24's within ~20° of the equator, and within a few minutes further north or south, where the
+/// sun rises at a shallower angle
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CelestialBody {
+    /// The length of the body's mean solar day. Expressed as (earth) days
+    pub solar_day: f64,
+    /// A moment of mean solar noon on the body's prime meridian. Expressed as days since J2000 (noon UTC on the 1st of
+    /// January 2000)
+    pub mean_noon_at_epoch: f64,
+    /// The sun's mean anomaly (how far it would have moved past perihelion in a circular orbit) at J2000. Expressed
+    /// as degrees
+    pub mean_anomaly_at_epoch: f64,
+    /// How far the sun's mean anomaly moves in a day. Expressed as degrees
+    pub mean_motion: f64,
+    /// The coefficients of the sines of one, two, and three times the mean anomaly, which sum to the equation of the
+    /// centre (the difference between the sun's true and mean anomaly). Expressed as degrees
+    pub equation_of_center: [f64; 3],
+    /// The angle of the perihelion from the equinox (from which the ecliptic longitude is measured), less 180°, at
+    /// J2000. Expressed as degrees
+    pub argument_of_perihelion: f64,
+    /// How far the perihelion moves against the equinox in a day. Expressed as degrees
+    pub perihelion_precession: f64,
+    /// The tilt of the body's axis at J2000. Expressed as degrees
+    pub obliquity: f64,
+    /// How quickly the tilt of the body's axis changes. Expressed as degrees per Julian century
+    pub obliquity_rate: f64,
+    /// The coefficients of the sines of the mean anomaly and of twice the mean anomaly (for the eccentricity of the
+    /// orbit), and of twice the ecliptic longitude (for the tilt of the axis), which make up the difference between
+    /// solar transit and mean solar noon. Expressed as the body's solar days
+    pub transit_correction: [f64; 3],
+    /// The altitude of the sun's centre at sunrise and sunset, from its apparent radius and the atmosphere's
+    /// refraction. Expressed as degrees
+    pub sunrise_angle: f64,
+    /// How far the horizon dips below the horizontal, for the square root of the elevation of the observer. Expressed
+    /// as arcminutes per square root of a meter
+    pub horizon_dip: f64,
+}
+
+impl CelestialBody {
+    /// The earth, as used for all of the crate's other calculations
+    pub const EARTH: Self = Self {
+        solar_day: 1.0,
+        mean_noon_at_epoch: 0.0,
+        mean_anomaly_at_epoch: 357.5291,
+        mean_motion: 0.98560028,
+        equation_of_center: [1.9148, 0.0200, 0.0003],
+        argument_of_perihelion: 102.9372,
+        //the perihelion moves ~1.72° a century against the equinox, mostly due to the precession of the equinoxes
+        perihelion_precession: 0.0000470935,
+        obliquity: 23.439291,
+        obliquity_rate: -0.0130042,
+        transit_correction: [0.0053, 0.0, 0.0069],
+        sunrise_angle: crate::SUNRISE_SUNSET_ANGLE,
+        horizon_dip: 2.076,
+    };
+
+    /// Mars, from Allison and McEwen's algorithm for Mars24, see <https://www.giss.nasa.gov/tools/mars24/help/algorithm.html>
+    ///
+    /// The mean solar noon is that of Coordinated Mars Time (MTC), converted from terrestrial time with a ΔT of 69
+    /// seconds. There's too little atmosphere for refraction to matter, so sunrise is when the top of the sun's
+    /// (smaller) disc is on the horizon
+    pub const MARS: Self = Self {
+        solar_day: 1.0274912517,
+        mean_noon_at_epoch: 5.013937,
+        mean_anomaly_at_epoch: 19.3871,
+        mean_motion: 0.52402073,
+        equation_of_center: [10.691, 0.623, 0.050],
+        argument_of_perihelion: 71.0,
+        perihelion_precession: 0.0000177660,
+        obliquity: 25.19,
+        obliquity_rate: 0.0,
+        transit_correction: [0.029697, 0.001731, 0.007947],
+        sunrise_angle: -0.17,
+        horizon_dip: 2.641,
+    };
+}
+
+impl Default for CelestialBody {
+    fn default() -> Self {
+        Self::EARTH
+    }
+}
+
+/// Calculates the approximate sunset and sunrise times on a given body, e.g. Mars, at a given latitude, longitude, and altitude
+///
+/// This is [sun_times](crate::sun_times) with the constants of the given body, so with [CelestialBody::EARTH] gives
+/// exactly the same times. The latitude and longitude are those on the body, with longitudes east of its prime
+/// meridian being positive, and the elevation is handled in the same way as in [sun_times](crate::sun_times)
+///
+/// As a day on Mars is ~40 minutes longer than one on the earth, every ~37 days there's a date without a sunrise (or
+/// sunset), on which that time is from the day before or after instead
+///
+/// # Arguments
+///
+/// * `body` - The body on which to calculate the times
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above the body's surface. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{sun_times_on, CelestialBody};
+///
+/// //Calculate the sunset and sunrise times at the Perseverance rover's landing site in Jezero crater
+/// let date = NaiveDate::from_ymd_opt(2021, 2, 18).unwrap();
+/// let (sunrise, sunset) = sun_times_on(&CelestialBody::MARS, date, 18.44, 77.45, 0.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_on(
+    body: &CelestialBody,
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    //the dip of the horizon is folded into the altitude crossed, as it depends on the size of the body
+    let horizon_angle =
+        body.sunrise_angle - body.horizon_dip * Float::sqrt(elevation.max(0.0)) / 60.0;
+    let horizon = Horizon::new(latitude, 0.0, horizon_angle);
+    let crossing = generic::pick_within_days(JulianDate::from_date(date), body.solar_day, |day| {
+        SunriseEquation::for_body(body, day, longitude / 360.0, delta_t(day))
+            .crossing_horizon(&horizon)
+    })?;
+    match crossing {
+        Crossing::At(rise, set) => Some((rise.to_datetime()?, set.to_datetime()?)),
+        Crossing::AlwaysAbove | Crossing::AlwaysBelow => None,
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+    use super::{sun_times_on, CelestialBody};
+
+    /// The altitude of the sun's centre on Mars, following Allison and McEwen's algorithm for Mars24 (without the
+    /// perturbations by the other planets, which are a few hundredths of a degree), see
+    /// <https://www.giss.nasa.gov/tools/mars24/help/algorithm.html>
+    fn mars24_altitude(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+        //days since J2000 in terrestrial time, taking ΔT as 69.184 seconds
+        let julian_date_tt = date_time.timestamp() as f64 / 86400.0 + 2440587.5 + 69.184 / 86400.0;
+        let days = julian_date_tt - 2451545.0;
+        let mean_anomaly = 19.3871 + 0.52402073 * days;
+        let fictitious_mean_sun = 270.3871 + 0.524038496 * days;
+        let sine = |multiple: f64| (multiple * mean_anomaly).to_radians().sin();
+        let equation_of_center = (10.691 + 3.0e-7 * days) * sine(1.0)
+            + 0.623 * sine(2.0)
+            + 0.050 * sine(3.0)
+            + 0.005 * sine(4.0)
+            + 0.0005 * sine(5.0);
+        let solar_longitude = (fictitious_mean_sun + equation_of_center).to_radians();
+        let equation_of_time = 2.861 * (2.0 * solar_longitude).sin()
+            - 0.071 * (4.0 * solar_longitude).sin()
+            + 0.002 * (6.0 * solar_longitude).sin()
+            - equation_of_center;
+        let mars_sol_date = (julian_date_tt - 2451549.5) / 1.0274912517 + 44796.0 - 0.0009626;
+        let coordinated_mars_time = 24.0 * mars_sol_date.rem_euclid(1.0);
+        let local_true_solar_time =
+            coordinated_mars_time + longitude / 15.0 + equation_of_time / 15.0;
+        let hour_angle = (15.0 * (local_true_solar_time - 12.0)).to_radians();
+        let declination = ((0.42565 * solar_longitude.sin()).asin().to_degrees()
+            + 0.25 * solar_longitude.sin())
+        .to_radians();
+        let latitude = latitude.to_radians();
+        (latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos())
+            .asin()
+            .to_degrees()
+    }
+
+    #[test]
+    fn earth_matches_sun_times() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        for day in (0..365).step_by(7) {
+            let date = start + Duration::days(day);
+            for (latitude, longitude) in [(53.38, -1.48), (-33.87, 151.21), (0.0, -179.0)] {
+                assert_eq!(
+                    sun_times_on(&CelestialBody::EARTH, date, latitude, longitude, 100.0),
+                    crate::sun_times(date, latitude, longitude, 100.0)
+                );
+            }
+        }
+        assert_eq!(CelestialBody::default(), CelestialBody::EARTH);
+    }
+
+    #[test]
+    fn mars_days_are_longer() {
+        let start = NaiveDate::from_ymd_opt(2021, 2, 18).unwrap();
+        let times: Vec<_> = (0..10)
+            .map(|day| {
+                sun_times_on(
+                    &CelestialBody::MARS,
+                    start + Duration::days(day),
+                    0.0,
+                    0.0,
+                    0.0,
+                )
+                .unwrap()
+            })
+            .collect();
+        for pair in times.windows(2) {
+            let ((rise, set), (next_rise, _)) = (pair[0], pair[1]);
+            //at the equator the sun is up for about half of each sol, which is 24h39m35s long. As the times are those
+            //within the UTC date, the sunset can be that of the sol before
+            assert!(((set - rise).num_minutes().abs() - 740).abs() < 10);
+            assert!(((next_rise - rise).num_minutes() - (24 * 60 + 40)).abs() < 2);
+        }
+    }
+
+    #[test]
+    fn mars_times_match_mars24() {
+        //the time near `time` at which the sun's centre is at the sunrise angle, according to Mars24
+        let mars24_crossing = |time: DateTime<Utc>, latitude: f64, longitude: f64| {
+            let above = |time| {
+                mars24_altitude(time, latitude, longitude) > CelestialBody::MARS.sunrise_angle
+            };
+            let rising = above(time + Duration::minutes(30));
+            let (mut before, mut after) =
+                (time - Duration::minutes(30), time + Duration::minutes(30));
+            while after - before > Duration::seconds(1) {
+                let middle = before + (after - before) / 2;
+                if above(middle) == rising {
+                    after = middle;
+                } else {
+                    before = middle;
+                }
+            }
+            before
+        };
+
+        //at the Perseverance rover's landing site in Jezero crater, and elsewhere, through a martian year
+        let start = NaiveDate::from_ymd_opt(2021, 2, 18).unwrap();
+        for (latitude, longitude) in [(18.44, 77.45), (0.0, 0.0), (-40.0, -120.0), (60.0, 10.0)] {
+            for day in (0..687).step_by(10) {
+                let date = start + Duration::days(day);
+                let Some((rise, set)) =
+                    sun_times_on(&CelestialBody::MARS, date, latitude, longitude, 0.0)
+                else {
+                    continue;
+                };
+                for time in [rise, set] {
+                    let expected = mars24_crossing(time, latitude, longitude);
+                    //within a minute near the equator, and a few where the sun rises at a shallower angle
+                    let tolerance = if latitude.abs() <= 20.0 { 60 } else { 300 };
+                    let error = (time - expected).num_seconds().abs();
+                    assert!(error < tolerance, "{time} is {error}s from {expected}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+
+use crate::generic::{self, Crossing, Horizon, SunriseEquation};
+use crate::{
+    times_on_local_date, JulianDate, JulianDays, Rounding, SunEdge, HORIZON_REFRACTION,
+    SECONDS_PER_DAY, SUNRISE_SUNSET_ANGLE,
+};
+
+/// Configures how sunrise and sunset are calculated, for when the defaults of [sun_times](crate::sun_times) aren't
+/// suitable
+///
+/// With no configuration, [calculate](SunTimesBuilder::calculate) gives exactly the same times as
+/// [sun_times](crate::sun_times) at sea level
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{FixedOffset, NaiveDate};
+/// use sun_times::SunTimesBuilder;
+///
+/// //Calculate when the sun rises over a 2° high ridge, in Japan Standard Time, at the top of Tokyo Skytree
+/// let jst = FixedOffset::east_opt(9 * 60 * 60).unwrap();
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = SunTimesBuilder::new()
+///     .horizon_angle(2.0)
+///     .elevation(634.0)
+///     .timezone(jst)
+///     .calculate(date, 35.71, 139.81)
+///     .unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunTimesBuilder<Tz: TimeZone = Utc> {
+    horizon_angle: f64,
+    refraction: bool,
+    edge: SunEdge,
+    elevation: f64,
+    //in seconds, or `None` to use the estimate for the date
+    delta_t: Option<f64>,
+    rounding: Rounding,
+    precision: Duration,
+    timezone: Tz,
+    //whether the date is interpreted in `timezone`, rather than in UTC
+    local_date: bool,
+}
+
+impl SunTimesBuilder {
+    /// Creates a builder which calculates the same times as [sun_times](crate::sun_times) at sea level
+    pub fn new() -> Self {
+        Self {
+            horizon_angle: 0.0,
+            refraction: true,
+            edge: SunEdge::UpperLimb,
+            elevation: 0.0,
+            delta_t: None,
+            rounding: Rounding::Nearest,
+            precision: Duration::seconds(1),
+            timezone: Utc,
+            local_date: false,
+        }
+    }
+}
+
+impl Default for SunTimesBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Tz: TimeZone> SunTimesBuilder<Tz> {
+    /// Sets the altitude of the horizon over which the sun rises and sets, e.g. for an obstructed horizon. Expressed
+    /// as degrees, negative values being below the horizontal. Defaults to 0
+    pub fn horizon_angle(mut self, horizon_angle: f64) -> Self {
+        self.horizon_angle = horizon_angle;
+        self
+    }
+
+    /// Sets whether the ~0.57° of atmospheric refraction at the horizon is accounted for. Defaults to `true`
+    pub fn refraction(mut self, refraction: bool) -> Self {
+        self.refraction = refraction;
+        self
+    }
+
+    /// Sets which part of the sun's disc marks sunrise and sunset as it crosses the horizon. Defaults to
+    /// [SunEdge::UpperLimb]
+    pub fn edge(mut self, edge: SunEdge) -> Self {
+        self.edge = edge;
+        self
+    }
+
+    /// Sets the elevation from which the sun is observed. Expressed as meters above sea level, with negative values
+    /// being ignored. Defaults to 0
+    pub fn elevation(mut self, elevation: f64) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    /// Sets ΔT (how far terrestrial time is ahead of UT1), e.g. from the IERS bulletins, rather than using the estimate
+    /// for the date. Expressed as seconds
+    ///
+    /// ΔT sets where the sun is along its orbit at a given moment. The sun moves only ~1° a day along it, so an error
+    /// of a minute in ΔT makes no visible difference to the times, but an error of hours (as for dates thousands of
+    /// years ago) can move them by a quarter of a minute or more
+    pub fn delta_t(mut self, delta_t: f64) -> Self {
+        self.delta_t = Some(delta_t);
+        self
+    }
+
+    /// Sets how the times are rounded, e.g. up to the next minute. Defaults to the nearest second
+    ///
+    /// The times are rounded to a multiple of `precision` since the Unix epoch, so rounding to the hour or more is
+    /// in UTC rather than in the configured timezone. See [JulianDate::to_datetime_rounded]
+    pub fn rounding(mut self, rounding: Rounding, precision: Duration) -> Self {
+        self.rounding = rounding;
+        self.precision = precision;
+        self
+    }
+
+    /// Sets the timezone in which dates are interpreted, and in which the times are returned, as in
+    /// [sun_times_tz](crate::sun_times_tz). Defaults to UTC
+    pub fn timezone<Tz2: TimeZone>(self, timezone: Tz2) -> SunTimesBuilder<Tz2> {
+        SunTimesBuilder {
+            horizon_angle: self.horizon_angle,
+            refraction: self.refraction,
+            edge: self.edge,
+            elevation: self.elevation,
+            delta_t: self.delta_t,
+            rounding: self.rounding,
+            precision: self.precision,
+            timezone,
+            local_date: true,
+        }
+    }
+
+    /// Calculates the approximate sunrise and sunset times at a given latitude and longitude
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date on which to calculate the sunset and sunrise, in the configured timezone
+    /// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+    /// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+    ///
+    /// # Return value
+    ///
+    /// Returns
+    ///  - `None` if the date is not representable in chrono (~5M years from now), or the sun does not both rise and set on that date
+    ///  - `Some((sunrise,sunset))` otherwise
+    pub fn calculate(
+        &self,
+        date: NaiveDate,
+        latitude: f64,
+        longitude: f64,
+    ) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
+        let horizon_angle = SUNRISE_SUNSET_ANGLE
+            + self.horizon_angle
+            + self.edge.angle_offset()
+            + if self.refraction {
+                0.0
+            } else {
+                HORIZON_REFRACTION
+            };
+        let times = |date| {
+            let julian_day = JulianDate::from_date(date);
+            let crossing = match self.delta_t {
+                None => generic::crossing_within_day(
+                    julian_day,
+                    latitude,
+                    longitude,
+                    self.elevation,
+                    horizon_angle,
+                ),
+                Some(delta_t) => {
+                    let delta_t = JulianDays(delta_t / SECONDS_PER_DAY as f64);
+                    let horizon = Horizon::new(latitude, self.elevation, horizon_angle);
+                    generic::pick_within_day(julian_day, |day| {
+                        SunriseEquation::with_delta_t(day, longitude, delta_t)
+                            .crossing_horizon(&horizon)
+                    })
+                }
+            };
+            let to_datetime =
+                |date: JulianDate| date.to_datetime_rounded(self.rounding, self.precision);
+            match crossing? {
+                Crossing::At(rise, set) => Some((to_datetime(rise)?, to_datetime(set)?)),
+                Crossing::AlwaysAbove | Crossing::AlwaysBelow => None,
+            }
+        };
+
+        if self.local_date {
+            times_on_local_date(date, &self.timezone, times)
+        } else {
+            let (rise, set) = times(date)?;
+            Some((
+                rise.with_timezone(&self.timezone),
+                set.with_timezone(&self.timezone),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, FixedOffset, NaiveDate, Timelike};
+
+    use super::SunTimesBuilder;
+    use crate::{Rounding, SunEdge};
+
+    #[test]
+    fn defaults_match_sun_times() {
+        let jst = FixedOffset::east_opt(9 * 60 * 60).unwrap();
+        for day in 1..=30 {
+            let date = NaiveDate::from_ymd_opt(2022, 6, day).unwrap();
+            assert_eq!(
+                SunTimesBuilder::new().calculate(date, 53.38, -1.48),
+                crate::sun_times(date, 53.38, -1.48, 0.0)
+            );
+            assert_eq!(
+                SunTimesBuilder::new()
+                    .elevation(40.0)
+                    .timezone(jst)
+                    .calculate(date, 35.68, 139.69),
+                crate::sun_times_tz(date, &jst, 35.68, 139.69, 40.0)
+            );
+        }
+    }
+
+    #[test]
+    fn raised_horizon_and_no_refraction_shorten_the_day() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (rise, set) = SunTimesBuilder::new()
+            .calculate(date, 53.38, -1.48)
+            .unwrap();
+        for builder in [
+            SunTimesBuilder::new().horizon_angle(2.0),
+            SunTimesBuilder::new().refraction(false),
+            SunTimesBuilder::new().edge(SunEdge::Center),
+        ] {
+            let (later_rise, earlier_set) = builder.calculate(date, 53.38, -1.48).unwrap();
+            assert!(later_rise > rise);
+            assert!(earlier_set < set);
+        }
+    }
+
+    #[test]
+    fn lower_limb_rises_after_the_centre() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let times = |edge| {
+            SunTimesBuilder::new()
+                .edge(edge)
+                .calculate(date, 53.38, -1.48)
+                .unwrap()
+        };
+        let (upper_rise, upper_set) = times(SunEdge::UpperLimb);
+        let (centre_rise, centre_set) = times(SunEdge::Center);
+        let (lower_rise, lower_set) = times(SunEdge::LowerLimb);
+        assert!(upper_rise < centre_rise && centre_rise < lower_rise);
+        assert!(lower_set < centre_set && centre_set < upper_set);
+        //the sun takes a few minutes to rise fully at this latitude
+        assert!((lower_rise - upper_rise).num_minutes() < 6);
+    }
+
+    #[test]
+    fn delta_t_moves_the_sun_along_its_orbit() {
+        let date = NaiveDate::from_ymd_opt(2022, 3, 20).unwrap();
+        let (rise, set) = SunTimesBuilder::new()
+            .calculate(date, 53.38, -1.48)
+            .unwrap();
+
+        //a minute or so either way barely moves the sun
+        for delta_t in [0.0, 69.0, 120.0] {
+            let (other_rise, other_set) = SunTimesBuilder::new()
+                .delta_t(delta_t)
+                .calculate(date, 53.38, -1.48)
+                .unwrap();
+            assert!((rise - other_rise).num_seconds().abs() <= 1);
+            assert!((set - other_set).num_seconds().abs() <= 1);
+        }
+
+        //while a day moves it to where it will be tomorrow, when the days are getting longer by ~4 minutes a day
+        let (later_rise, later_set) = SunTimesBuilder::new()
+            .delta_t(24.0 * 60.0 * 60.0)
+            .calculate(date, 53.38, -1.48)
+            .unwrap();
+        assert!((rise - later_rise).num_seconds() > 60);
+        assert!((later_set - set).num_seconds() > 60);
+    }
+
+    #[test]
+    fn rounding_to_the_minute() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (rise, set) = SunTimesBuilder::new()
+            .calculate(date, 53.38, -1.48)
+            .unwrap();
+        let rounded = |rounding| {
+            SunTimesBuilder::new()
+                .rounding(rounding, Duration::minutes(1))
+                .calculate(date, 53.38, -1.48)
+                .unwrap()
+        };
+
+        let (up_rise, up_set) = rounded(Rounding::Up);
+        let (down_rise, down_set) = rounded(Rounding::Down);
+        let (nearest_rise, nearest_set) = rounded(Rounding::Nearest);
+        for time in [
+            up_rise,
+            up_set,
+            down_rise,
+            down_set,
+            nearest_rise,
+            nearest_set,
+        ] {
+            assert_eq!(time.second(), 0);
+        }
+        //the default times are already rounded to the nearest second
+        let slack = Duration::seconds(1);
+        assert!(down_rise <= rise + slack && rise <= up_rise + slack);
+        assert!(down_set <= set + slack && set <= up_set + slack);
+        assert_eq!(up_rise - down_rise, Duration::minutes(1));
+        assert!((nearest_rise - rise).num_seconds().abs() <= 30);
+        assert!((nearest_set - set).num_seconds().abs() <= 30);
+    }
+}
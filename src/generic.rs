@@ -0,0 +1,737 @@
+//! Versions of the calculations which are generic over the floating point type used
+//!
+//! The functions in the crate root always calculate with `f64`, but those here can also be used with `f32`, e.g. to
+//! halve the memory used when plotting a year of data on embedded or WASM targets. Results at `f32` stay within a
+//! few minutes of those at `f64`, which is plenty for visualisation
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, Utc};
+use num_traits::Float;
+
+use crate::{delta_t, CelestialBody, JulianDate, JulianDays, SunPosition, JAN_2000};
+#[cfg(feature = "chrono")]
+use crate::{SunState, SUNRISE_SUNSET_ANGLE};
+
+/// Converts a constant into the float type being calculated with
+#[inline]
+fn constant<F: Float>(value: f64) -> F {
+    F::from(value).expect("constants are representable in every float type")
+}
+
+/// Converts a calculated value back into an `f64`
+#[inline]
+fn to_f64<F: Float>(value: F) -> f64 {
+    value
+        .to_f64()
+        .expect("every float type is representable as an f64")
+}
+
+/// The obliquity of the ecliptic (the tilt of the earth's axis) `days_since_2000` days after J2000, expressed as
+/// degrees
+///
+/// This is the linear term of Laskar's expression (see Meeus' Astronomical Algorithms, chapter 22), decreasing by
+/// ~0.013° a century from 23.4393° at J2000. The higher order terms are under a thousandth of a degree until 2500.
+/// It's held at its value 10,000 years either side of J2000, beyond which the expression isn't valid, as the tilt only
+/// varies between ~22° and ~24.5°
+#[inline]
+pub(crate) fn obliquity_of_the_ecliptic<F: Float>(days_since_2000: F) -> F {
+    obliquity(&CelestialBody::EARTH, days_since_2000)
+}
+
+/// The tilt of the given body's axis `days_since_2000` days after J2000, expressed as degrees, see
+/// [obliquity_of_the_ecliptic]
+#[inline]
+fn obliquity<F: Float>(body: &CelestialBody, days_since_2000: F) -> F {
+    let julian_centuries = (days_since_2000 / constant(36525.0))
+        .max(constant(-100.0))
+        .min(constant(100.0));
+    constant::<F>(body.obliquity) + constant::<F>(body.obliquity_rate) * julian_centuries
+}
+
+/// The remainder of `value` divided by `modulus`, which unlike `%` is never negative. This is available without std,
+/// unlike `f64::rem_euclid`
+#[inline]
+pub(crate) fn rem_euclid<F: Float>(value: F, modulus: F) -> F {
+    let remainder = value % modulus;
+    if remainder < F::zero() {
+        remainder + modulus
+    } else {
+        remainder
+    }
+}
+
+/// Generic version of [crate::sun_times]
+#[cfg(feature = "chrono")]
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building, with f32s
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times::generic::sun_times(date, 53.38f32, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+pub fn sun_times<F: Float>(
+    date: NaiveDate,
+    latitude: F,
+    longitude: F,
+    elevation: F,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    times_within_day(
+        date,
+        latitude,
+        longitude,
+        elevation,
+        constant(SUNRISE_SUNSET_ANGLE),
+    )
+}
+
+/// Calculates the times at which the sun's centre crosses the given altitude within the given UTC date, see
+/// [crossing_within_day]
+#[cfg(feature = "chrono")]
+pub(crate) fn times_within_day<F: Float>(
+    date: NaiveDate,
+    latitude: F,
+    longitude: F,
+    elevation: F,
+    horizon_angle: F,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let julian_day = JulianDate::from_date(date);
+    match crossing_within_day(julian_day, latitude, longitude, elevation, horizon_angle)? {
+        Crossing::At(rise, set) => Some((rise.to_datetime()?, set.to_datetime()?)),
+        Crossing::AlwaysAbove | Crossing::AlwaysBelow => None,
+    }
+}
+
+#[cfg(feature = "chrono")]
+pub(crate) fn sun_state_with_angle<F: Float>(
+    date: NaiveDate,
+    latitude: F,
+    longitude: F,
+    elevation: F,
+    horizon_angle: F,
+) -> Option<SunState> {
+    let julian_day = JulianDate::from_date(date);
+    Some(
+        match crossing(julian_day, latitude, longitude, elevation, horizon_angle)? {
+            Crossing::At(rise, set) => SunState::Rises {
+                rise: rise.to_datetime()?,
+                set: set.to_datetime()?,
+            },
+            Crossing::AlwaysAbove => SunState::AlwaysUp,
+            Crossing::AlwaysBelow => SunState::AlwaysDown,
+        },
+    )
+}
+
+/// How far outside of [-1,1] the cosine of the hour angle can go before the sun is taken to never cross the altitude.
+/// Just outside of the range is floating point error, rather than polar day/night
+const POLAR_THRESHOLD: f64 = 1.0001;
+
+/// When the sun's centre crosses a given altitude on a given day
+pub(crate) enum Crossing {
+    /// The sun rises above the altitude and then sets below it again at the given times
+    At(JulianDate, JulianDate),
+    /// The sun stays above the altitude for the whole day
+    AlwaysAbove,
+    /// The sun stays below the altitude for the whole day
+    AlwaysBelow,
+}
+
+/// Calculates when the sun's centre crosses the given altitude (`horizon_angle`, in degrees) on the UTC day containing `julian_day`, corrected for elevation
+///
+/// Returns `None` if the crossing can't be calculated, e.g. due to NaN inputs
+#[cfg(feature = "chrono")]
+pub(crate) fn crossing<F: Float>(
+    julian_day: JulianDate,
+    latitude: F,
+    longitude: F,
+    elevation: F,
+    horizon_angle: F,
+) -> Option<Crossing> {
+    SunriseEquation::new(julian_day, longitude).crossing(latitude, elevation, horizon_angle)
+}
+
+/// Calculates when the sun's centre crosses the given altitude, like [crossing], but picks the rise and set which fall
+/// within the UTC day containing `julian_day`
+///
+/// Away from Greenwich the solar day (centred on solar noon) straddles two UTC days, so the rise or set may need to
+/// come from the solar day before or after. When there's no rise or set within the UTC day at all (as happens when
+/// they drift across midnight, or close to the start or end of polar day/night) the solar day's own rise and set are
+/// used
+pub(crate) fn crossing_within_day<F: Float>(
+    julian_day: JulianDate,
+    latitude: F,
+    longitude: F,
+    elevation: F,
+    horizon_angle: F,
+) -> Option<Crossing> {
+    //the observer's side of the calculation is the same on each of the days tried
+    let horizon = Horizon::new(latitude, elevation, horizon_angle);
+    pick_within_day(julian_day, |day| {
+        SunriseEquation::new(day, longitude).crossing_horizon(&horizon)
+    })
+}
+
+/// Picks the rise and set which fall within the UTC day containing `julian_day`, from those calculated by `crossing`
+/// for that solar day and its neighbours, see [crossing_within_day]
+pub(crate) fn pick_within_day(
+    julian_day: JulianDate,
+    crossing: impl Fn(JulianDate) -> Option<Crossing>,
+) -> Option<Crossing> {
+    pick_within_days(julian_day, 1.0, crossing)
+}
+
+/// Like [pick_within_day], for a body whose solar days are `solar_day` (earth) days long, so that the neighbouring
+/// solar days are found `solar_day` either side
+pub(crate) fn pick_within_days(
+    julian_day: JulianDate,
+    solar_day: f64,
+    crossing: impl Fn(JulianDate) -> Option<Crossing>,
+) -> Option<Crossing> {
+    let start_of_day = julian_day.start_of_day();
+    let end_of_day = JulianDate(start_of_day.0 + 1.0);
+    let within_day = |date: JulianDate| start_of_day <= date && date < end_of_day;
+
+    let (own_rise, own_set) = match crossing(start_of_day)? {
+        Crossing::At(rise, set) => (rise, set),
+        polar => return Some(polar),
+    };
+
+    let mut rise = Some(own_rise).filter(|&rise| within_day(rise));
+    let mut set = Some(own_set).filter(|&set| within_day(set));
+    for offset in [-solar_day, solar_day] {
+        let neighbour = JulianDate(start_of_day.0 + offset);
+        if let Some(Crossing::At(neighbour_rise, neighbour_set)) = crossing(neighbour) {
+            rise = rise.or(Some(neighbour_rise).filter(|&rise| within_day(rise)));
+            set = set.or(Some(neighbour_set).filter(|&set| within_day(set)));
+        }
+    }
+
+    Some(match (rise, set) {
+        (Some(rise), Some(set)) => Crossing::At(rise, set),
+        _ => Crossing::At(own_rise, own_set),
+    })
+}
+
+/// Calculates the angle the sun has moved from perihelion, from the angle it would have moved if the body's orbit
+/// were circular, both expressed as degrees
+#[inline]
+fn true_anomaly<F: Float>(mean_anomaly: F, body: &CelestialBody) -> F {
+    //the equation of the centre
+    let [first, second, third] = body.equation_of_center;
+    let center = constant::<F>(first) * mean_anomaly.to_radians().sin()
+        + constant::<F>(second) * (constant::<F>(2.0) * mean_anomaly).to_radians().sin()
+        + constant::<F>(third) * (constant::<F>(3.0) * mean_anomaly).to_radians().sin();
+    mean_anomaly + center
+}
+
+/// The intermediate values of the sunrise equation for a given (UTC) day and longitude
+///
+/// See <https://en.wikipedia.org/wiki/Sunrise_equation>
+pub(crate) struct SunriseEquation<F> {
+    /// Expressed as days since J2000
+    pub(crate) mean_solar_time: F,
+    /// Expressed as degrees
+    pub(crate) solar_mean_anomaly: F,
+    /// Expressed as degrees
+    pub(crate) ecliptic_longitude: F,
+    /// Expressed as radians
+    pub(crate) declination: F,
+    /// Expressed as degrees
+    obliquity: F,
+    /// The length of the solar day. Expressed as (earth) days
+    solar_day: f64,
+    /// See [CelestialBody::transit_correction]
+    transit_coefficients: [f64; 3],
+}
+
+impl<F: Float> SunriseEquation<F> {
+    /// `julian_day` may be any moment within the day
+    pub(crate) fn new(julian_day: JulianDate, longitude: F) -> Self {
+        Self::with_delta_t(julian_day, longitude, delta_t(julian_day.start_of_day()))
+    }
+
+    /// Like [SunriseEquation::new], but with the given ΔT rather than the estimate for the date
+    pub(crate) fn with_delta_t(julian_day: JulianDate, longitude: F, delta_t: JulianDays) -> Self {
+        Self::for_body(
+            &CelestialBody::EARTH,
+            julian_day.start_of_day(),
+            longitude / constant(360.0),
+            delta_t,
+        )
+    }
+
+    /// Like [SunriseEquation::new], with the longitude expressed as radians
+    #[cfg(feature = "chrono")]
+    pub(crate) fn new_radians(julian_day: JulianDate, longitude: F) -> Self {
+        Self::for_body(
+            &CelestialBody::EARTH,
+            julian_day.start_of_day(),
+            longitude / constant(core::f64::consts::TAU),
+            delta_t(julian_day.start_of_day()),
+        )
+    }
+
+    /// Like [SunriseEquation::with_delta_t], but on the given body, for its solar day with the first mean solar noon
+    /// (at its prime meridian) at or after `start`. `turns_east` is the longitude as a fraction of a full turn
+    pub(crate) fn for_body(
+        body: &CelestialBody,
+        start: JulianDate,
+        turns_east: F,
+        delta_t: JulianDays,
+    ) -> Self {
+        //the sunrise equation works in whole solar days since J2000 (for the earth, the mean solar noon at Greenwich)
+        let solar_days =
+            JulianDays(((start - JAN_2000).0 - body.mean_noon_at_epoch) / body.solar_day)
+                .ceil_days();
+        let days_since_2000 = body.mean_noon_at_epoch + solar_days * body.solar_day;
+        Self::from_days(
+            body,
+            constant(days_since_2000),
+            constant(delta_t.0),
+            turns_east,
+        )
+    }
+
+    /// Calculates the values at the given moment, rather than for a whole day, so that the sun's position doesn't
+    /// jump from one day to the next
+    pub(crate) fn at(julian_date: JulianDate) -> Self {
+        let days_since_2000 = (julian_date - JAN_2000).0;
+        Self::from_days(
+            &CelestialBody::EARTH,
+            constant(days_since_2000),
+            constant(delta_t(julian_date).0),
+            F::zero(),
+        )
+    }
+
+    /// `days_since_2000` is in UT, which the earth's rotation (and so the mean solar time) follows, while the sun's
+    /// position along its orbit is calculated in terrestrial time, `delta_t` days ahead
+    fn from_days(body: &CelestialBody, days_since_2000: F, delta_t: F, turns_east: F) -> Self {
+        //the mean solar time moves back by a solar day for every full turn east
+        let mean_solar_time = days_since_2000 - turns_east * constant(body.solar_day);
+        let orbital_days = mean_solar_time + delta_t;
+        let solar_mean_anomaly = rem_euclid(
+            constant::<F>(body.mean_anomaly_at_epoch)
+                + constant::<F>(body.mean_motion) * orbital_days,
+            constant(360.0),
+        );
+        //the perihelion (from which the mean anomaly is measured) moves against the equinox (from which the ecliptic
+        //longitude is measured)
+        let ecliptic_longitude = rem_euclid(
+            true_anomaly(solar_mean_anomaly, body)
+                + constant(180.0)
+                + constant(body.argument_of_perihelion)
+                + constant::<F>(body.perihelion_precession) * orbital_days,
+            constant(360.0),
+        );
+
+        let obliquity = obliquity(body, days_since_2000 + delta_t);
+        let declination =
+            (ecliptic_longitude.to_radians().sin() * obliquity.to_radians().sin()).asin();
+
+        Self {
+            mean_solar_time,
+            solar_mean_anomaly,
+            ecliptic_longitude,
+            declination,
+            obliquity,
+            solar_day: body.solar_day,
+            transit_coefficients: body.transit_correction,
+        }
+    }
+
+    /// Calculates the values for a given longitude from those at Greenwich (longitude 0) on a day and its neighbours,
+    /// by interpolating between them, for when the same day is needed at many longitudes
+    ///
+    /// The sun's position changes slowly enough that this is within ~0.0001° of [SunriseEquation::new]
+    #[cfg(all(feature = "chrono", feature = "std"))]
+    pub(crate) fn interpolate_longitude([previous, this, next]: [&Self; 3], longitude: F) -> Self {
+        //linearly interpolates between two angles in degrees, the short way round
+        let between = |from: F, to: F, fraction: F| {
+            let difference =
+                rem_euclid(to - from + constant(540.0), constant(360.0)) - constant(180.0);
+            rem_euclid(from + difference * fraction, constant(360.0))
+        };
+
+        //the mean solar time moves back by a day for every 360° east
+        let offset = -longitude / constant(360.0);
+        let (neighbour, fraction) = if offset < F::zero() {
+            (previous, -offset)
+        } else {
+            (next, offset)
+        };
+        Self {
+            mean_solar_time: this.mean_solar_time + offset,
+            solar_mean_anomaly: between(
+                this.solar_mean_anomaly,
+                neighbour.solar_mean_anomaly,
+                fraction,
+            ),
+            ecliptic_longitude: between(
+                this.ecliptic_longitude,
+                neighbour.ecliptic_longitude,
+                fraction,
+            ),
+            declination: this.declination + (neighbour.declination - this.declination) * fraction,
+            obliquity: this.obliquity + (neighbour.obliquity - this.obliquity) * fraction,
+            solar_day: this.solar_day,
+            transit_coefficients: this.transit_coefficients,
+        }
+    }
+
+    /// The distance between the earth and the sun. Expressed as astronomical units
+    #[cfg(feature = "chrono")]
+    pub(crate) fn distance(&self) -> F {
+        //see https://en.wikipedia.org/wiki/True_anomaly#Radius_from_true_anomaly
+        let eccentricity = constant::<F>(0.016709);
+        (F::one() - eccentricity * eccentricity)
+            / (F::one()
+                + eccentricity
+                    * true_anomaly(self.solar_mean_anomaly, &CelestialBody::EARTH)
+                        .to_radians()
+                        .cos())
+    }
+
+    /// Expressed as degrees, in the range [0, 360)
+    #[inline]
+    pub(crate) fn right_ascension(&self) -> F {
+        let (sin_longitude, cos_longitude) = self.ecliptic_longitude.to_radians().sin_cos();
+        let right_ascension = (sin_longitude * self.obliquity.to_radians().cos())
+            .atan2(cos_longitude)
+            .to_degrees();
+        rem_euclid(right_ascension, constant(360.0))
+    }
+
+    #[inline]
+    pub(crate) fn solar_transit(&self) -> JulianDate {
+        JulianDate(
+            JAN_2000.0
+                + to_f64(
+                    self.mean_solar_time + self.transit_correction() * constant(self.solar_day),
+                ),
+        )
+    }
+
+    /// The difference (in solar days) between solar transit and mean solar noon, due to the eccentricity of the
+    /// body's orbit and its axial tilt
+    #[inline]
+    pub(crate) fn transit_correction(&self) -> F {
+        let [anomaly, double_anomaly, longitude] = self.transit_coefficients;
+        let mut correction = constant::<F>(anomaly) * self.solar_mean_anomaly.to_radians().sin()
+            - constant::<F>(longitude)
+                * (constant::<F>(2.0) * self.ecliptic_longitude)
+                    .to_radians()
+                    .sin();
+        if double_anomaly != 0.0 {
+            correction = correction
+                + constant::<F>(double_anomaly)
+                    * (constant::<F>(2.0) * self.solar_mean_anomaly)
+                        .to_radians()
+                        .sin();
+        }
+        correction
+    }
+
+    /// Calculates when the sun's centre crosses the given altitude on this day, see [crossing]
+    #[cfg(feature = "chrono")]
+    pub(crate) fn crossing(&self, latitude: F, elevation: F, horizon_angle: F) -> Option<Crossing> {
+        self.crossing_horizon(&Horizon::new(latitude, elevation, horizon_angle))
+    }
+
+    /// Like [SunriseEquation::crossing], for when the same horizon is crossed on many days
+    #[inline]
+    pub(crate) fn crossing_horizon(&self, horizon: &Horizon<F>) -> Option<Crossing> {
+        let cos_event_hour_angle = horizon.cos_hour_angle(self.declination);
+
+        if cos_event_hour_angle.is_nan() {
+            return None;
+        }
+
+        //well outside of [-1,1] the sun never crosses the horizon angle, the sign tells us which side it stays on
+        if cos_event_hour_angle < -constant::<F>(POLAR_THRESHOLD) {
+            return Some(Crossing::AlwaysAbove);
+        } else if cos_event_hour_angle > constant(POLAR_THRESHOLD) {
+            return Some(Crossing::AlwaysBelow);
+        }
+
+        let event_hour_angle = clamped_hour_angle(cos_event_hour_angle);
+
+        let solar_transit_julian = self.solar_transit();
+        let half_day = to_f64(event_hour_angle) / 360.0 * self.solar_day;
+
+        let julian_rise = JulianDate(solar_transit_julian.0 - half_day);
+        let julian_set = JulianDate(solar_transit_julian.0 + half_day);
+        Some(Crossing::At(julian_rise, julian_set))
+    }
+}
+
+/// The parts of the sunrise equation which only depend on the observer, rather than the day
+pub(crate) struct Horizon<F> {
+    sin_latitude: F,
+    cos_latitude: F,
+    /// The sine of the altitude crossed, after correcting for elevation
+    sin_altitude: F,
+}
+
+impl<F: Float> Horizon<F> {
+    /// `latitude` and `horizon_angle` are expressed as degrees, and `elevation` as meters above sea level
+    #[inline]
+    pub(crate) fn new(latitude: F, elevation: F, horizon_angle: F) -> Self {
+        Self::from_radians(latitude.to_radians(), elevation, horizon_angle)
+    }
+
+    /// Like [Horizon::new], with the latitude expressed as radians
+    #[inline]
+    pub(crate) fn from_radians(latitude: F, elevation: F, horizon_angle: F) -> Self {
+        let (sin_latitude, cos_latitude) = latitude.sin_cos();
+        Self {
+            sin_latitude,
+            cos_latitude,
+            //the dip is only approximate far above the surface, where it would grow beyond straight down
+            sin_altitude: (horizon_angle + elevation_correction(elevation))
+                .max(constant(-90.0))
+                .to_radians()
+                .sin(),
+        }
+    }
+
+    /// The cosine of the hour angle at which the sun crosses the horizon, given its declination (in radians). This is
+    /// outside of [-1, 1] if the sun never reaches it
+    #[inline]
+    fn cos_hour_angle(&self, declination: F) -> F {
+        let (sin_declination, cos_declination) = declination.sin_cos();
+        (self.sin_altitude - self.sin_latitude * sin_declination)
+            / (self.cos_latitude * cos_declination)
+    }
+
+    /// The hour angle (in degrees) at which the sun crosses the horizon, given its declination (in radians), or
+    /// `None` if it never reaches it
+    #[cfg(feature = "chrono")]
+    #[inline]
+    pub(crate) fn hour_angle(&self, declination: F) -> Option<F> {
+        let cos_hour_angle = self.cos_hour_angle(declination);
+        if cos_hour_angle.abs() > constant(POLAR_THRESHOLD) || cos_hour_angle.is_nan() {
+            return None;
+        }
+        Some(clamped_hour_angle(cos_hour_angle))
+    }
+}
+
+/// The hour angle (in degrees) with the given cosine, clamped so that rounding errors right at the edge of polar
+/// day/night don't give a NaN
+#[inline]
+fn clamped_hour_angle<F: Float>(cos_hour_angle: F) -> F {
+    cos_hour_angle
+        .max(-F::one())
+        .min(F::one())
+        .acos()
+        .to_degrees()
+}
+
+/// The dip of the horizon (in degrees) seen from the given elevation, in meters above sea level
+#[inline]
+fn elevation_correction<F: Float>(elevation: F) -> F {
+    //elevations below sea level will have minimal atmospheric refraction + the
+    //calculation is broken below sea level, so treat negative elevations as being at sea level
+    let elevation = elevation.max(F::zero());
+    constant::<F>(-2.076) * elevation.sqrt() / constant(60.0)
+}
+
+/// Generic version of [crate::altitude]
+#[cfg(feature = "chrono")]
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+///
+/// //Calculate the altitude of the sun at Sheffield university's new computer science building, with f32s
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let altitude = sun_times::generic::altitude(date_time, 53.38f32, -1.48);
+/// println!("Altitude: {}", altitude);
+/// ```
+pub fn altitude<F: Float>(date_time: DateTime<Utc>, latitude: F, longitude: F) -> F {
+    sun_position(date_time, latitude, longitude).altitude
+}
+
+/// Generic version of [crate::azimuth]
+#[cfg(feature = "chrono")]
+pub fn azimuth<F: Float>(date_time: DateTime<Utc>, latitude: F, longitude: F) -> F {
+    sun_position(date_time, latitude, longitude).azimuth
+}
+
+/// Generic version of [crate::sun_position]
+#[cfg(feature = "chrono")]
+pub fn sun_position<F: Float>(
+    date_time: DateTime<Utc>,
+    latitude: F,
+    longitude: F,
+) -> SunPosition<F> {
+    position(JulianDate::from(date_time), latitude, longitude)
+}
+
+/// Calculates the position of the sun at a given place and moment
+pub(crate) fn position<F: Float>(
+    julian_date: JulianDate,
+    latitude: F,
+    longitude: F,
+) -> SunPosition<F> {
+    let (declination, local_hour_angle) = declination_and_hour_angle(julian_date, longitude);
+    horizontal_position(declination, local_hour_angle, latitude)
+}
+
+/// Calculates the position of the sun in the sky from its declination (in radians) and local hour angle (in degrees)
+#[inline]
+pub(crate) fn horizontal_position<F: Float>(
+    declination: F,
+    local_hour_angle: F,
+    latitude: F,
+) -> SunPosition<F> {
+    let (altitude, azimuth_from_south) = horizontal_angles(
+        declination,
+        local_hour_angle.to_radians(),
+        latitude.to_radians(),
+    );
+    SunPosition {
+        altitude: altitude.to_degrees(),
+        azimuth: rem_euclid(
+            azimuth_from_south.to_degrees() + constant(180.0),
+            constant(360.0),
+        ),
+    }
+}
+
+/// Calculates the altitude and azimuth (clockwise from north, in [0, 2π)) of the sun at a given place and moment, all
+/// in radians
+#[cfg(feature = "chrono")]
+pub(crate) fn position_radians<F: Float>(
+    julian_date: JulianDate,
+    latitude: F,
+    longitude: F,
+) -> (F, F) {
+    //the hour angle at greenwich comes from the sidereal time in degrees, and the longitude is added on in radians
+    let (declination, greenwich_hour_angle) = declination_and_hour_angle(julian_date, F::zero());
+    let local_hour_angle = greenwich_hour_angle.to_radians() + longitude;
+    let (altitude, azimuth_from_south) = horizontal_angles(declination, local_hour_angle, latitude);
+    let azimuth = rem_euclid(
+        azimuth_from_south + constant(core::f64::consts::PI),
+        constant(core::f64::consts::TAU),
+    );
+    (altitude, azimuth)
+}
+
+/// Calculates the altitude and azimuth (clockwise from south) of the sun from its declination, local hour angle, and
+/// the latitude, all in radians
+#[inline]
+fn horizontal_angles<F: Float>(declination: F, local_hour_angle: F, latitude: F) -> (F, F) {
+    //see https://en.wikipedia.org/wiki/Solar_azimuth_angle
+
+    let (sin_latitude, cos_latitude) = latitude.sin_cos();
+    let (sin_declination, cos_declination) = declination.sin_cos();
+    let (sin_hour_angle, cos_hour_angle) = local_hour_angle.sin_cos();
+
+    //rounding can take this just past ±1 when the sun is directly overhead (or underfoot), which asin gives NaN for
+    let sin_altitude = ((sin_latitude * sin_declination)
+        + (cos_latitude * cos_declination * cos_hour_angle))
+        .max(-F::one())
+        .min(F::one());
+
+    //atan2 gives the azimuth measured from the south, which the callers rotate to be measured from the north
+    let azimuth_from_south = sin_hour_angle
+        .atan2(cos_hour_angle * sin_latitude - sin_declination / cos_declination * cos_latitude);
+
+    (sin_altitude.asin(), azimuth_from_south)
+}
+
+/// Calculates the sun's declination (in radians) and local hour angle (in degrees) at a given longitude and moment
+pub(crate) fn declination_and_hour_angle<F: Float>(
+    julian_date: JulianDate,
+    longitude: F,
+) -> (F, F) {
+    //see https://en.wikipedia.org/wiki/Sunrise_equation
+    //see https://en.wikipedia.org/wiki/Astronomical_coordinate_systems
+    //see http://www.stargazing.net/kepler/altaz.html
+
+    let equation = SunriseEquation::<F>::at(julian_date);
+
+    let local_sideral_time = constant::<F>(greenwich_sidereal_time(julian_date)) + longitude;
+    let local_hour_angle = local_sideral_time - equation.right_ascension();
+
+    (equation.declination, local_hour_angle)
+}
+
+/// Calculates the sidereal time at Greenwich (in degrees) at a given moment
+///
+/// This is reduced to [0, 360) as an `f64`, as it grows far too large to keep full precision in an f32
+#[inline]
+pub(crate) fn greenwich_sidereal_time(julian_date: JulianDate) -> f64 {
+    //see https://en.wikipedia.org/wiki/Sidereal_time
+    rem_euclid(
+        280.46061837 + 360.98564736629 * (julian_date - JAN_2000).0,
+        360.0,
+    )
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+
+    #[test]
+    fn f32_sun_times_stay_close_to_f64() {
+        for ordinal in 1..=365 {
+            let date = NaiveDate::from_yo_opt(2022, ordinal).unwrap();
+            let (rise_f64, set_f64) = super::sun_times(date, 53.38f64, -1.48, 100.0).unwrap();
+            let (rise_f32, set_f32) = super::sun_times(date, 53.38f32, -1.48, 100.0).unwrap();
+            assert!((rise_f64 - rise_f32).num_seconds().abs() < 3 * 60);
+            assert!((set_f64 - set_f32).num_seconds().abs() < 3 * 60);
+        }
+    }
+
+    #[test]
+    fn hour_angle_is_clamped_at_the_edge_of_polar_day() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        let equation = super::SunriseEquation::new(crate::JulianDate::from_date(date), 0.0f64);
+        //the latitude at which the sun just touches the horizon angle at midnight
+        let edge = 90.0 + crate::SUNRISE_SUNSET_ANGLE - equation.declination.to_degrees();
+
+        let Some(super::Crossing::At(rise, set)) =
+            equation.crossing(edge + 1e-6, 0.0, crate::SUNRISE_SUNSET_ANGLE)
+        else {
+            panic!("expected the sun to rise and set just inside the threshold");
+        };
+        assert!(((set - rise).0 - 1.0).abs() < 1e-9);
+        assert!(matches!(
+            equation.crossing(edge + 0.1, 0.0, crate::SUNRISE_SUNSET_ANGLE),
+            Some(super::Crossing::AlwaysAbove)
+        ));
+    }
+
+    #[test]
+    fn altitude_is_continuous_through_the_day() {
+        let start = NaiveDate::from_ymd_opt(2022, 3, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+        let mut previous = super::altitude(start, 53.38f64, -1.48);
+        for minute in 1..=48 * 60 {
+            let altitude = super::altitude(start + Duration::minutes(minute), 53.38, -1.48);
+            //the sun never moves by more than 0.25° a minute
+            assert!((altitude - previous).abs() < 0.25);
+            previous = altitude;
+        }
+    }
+
+    #[test]
+    fn obliquity_decreases_slowly() {
+        //from Meeus' Astronomical Algorithms, table 22.A
+        assert!((super::obliquity_of_the_ecliptic(0.0_f64) - 23.4393).abs() < 0.0001);
+        assert!((super::obliquity_of_the_ecliptic(-36525.0_f64) - 23.4523).abs() < 0.0001);
+        //around now it's within a few thousandths of the rounded value previously used
+        assert!((super::obliquity_of_the_ecliptic(8766.0_f64) - 23.44).abs() < 0.005);
+    }
+}
@@ -0,0 +1,245 @@
+//! Versions of the calculations using the jiff crate's date and time types, rather than chrono's
+//!
+//! These are the same calculations as the functions of the same name in the crate root, see their documentation for
+//! details. Dates are in UTC unless a timezone is given, and returned times are `Timestamp`s, or `Zoned`s in the given
+//! timezone
+
+use ::jiff::civil::Date;
+use ::jiff::tz::TimeZone;
+use ::jiff::{Timestamp, Zoned};
+
+use crate::generic::{self, Crossing};
+use crate::{JulianDate, SunPosition, Twilight, SUNRISE_SUNSET_ANGLE};
+
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in jiff (outside of the years ±9999), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use jiff::civil::date;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building
+/// let (sunrise, sunset) = sun_times::jiff::sun_times(date(2022, 6, 1), 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+pub fn sun_times(
+    date: Date,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(Timestamp, Timestamp)> {
+    sun_times_with_angle(date, latitude, longitude, elevation, SUNRISE_SUNSET_ANGLE)
+}
+
+/// Calculates the approximate sunset and sunrise times on a given date in a given timezone, at a given latitude, longitude, and altitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in `tz`
+/// * `tz` - The timezone in which `date` is interpreted, and in which the times are returned
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in jiff (outside of the years ±9999), or the sun does not both rise and set on that date in `tz`
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use jiff::civil::date;
+/// use jiff::tz::{offset, TimeZone};
+///
+/// //Calculate the sunset and sunrise times in Tokyo, in Japan Standard Time
+/// let jst = TimeZone::fixed(offset(9));
+/// let (sunrise, sunset) =
+///     sun_times::jiff::sun_times_zoned(date(2022, 6, 1), &jst, 35.68, 139.69, 40.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+pub fn sun_times_zoned(
+    date: Date,
+    tz: &TimeZone,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(Zoned, Zoned)> {
+    //as in the crate root's sun_times_tz, look at the times on each UTC date which the local date overlaps
+    let dates = [date.yesterday().ok()?, date, date.tomorrow().ok()?];
+    let local_times = || {
+        dates
+            .into_iter()
+            .filter_map(|date| sun_times(date, latitude, longitude, elevation))
+            .map(|(rise, set)| (rise.to_zoned(tz.clone()), set.to_zoned(tz.clone())))
+    };
+    let (rise, _) = local_times().find(|(rise, _)| rise.date() == date)?;
+    let (_, set) = local_times().find(|(_, set)| set.date() == date)?;
+    Some((rise, set))
+}
+
+/// Calculates the approximate times of dawn and dusk, for a given kind of twilight, at a given latitude, longitude, and altitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate dawn and dusk, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+/// * `kind` - The kind of twilight to calculate the times for
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in jiff (outside of the years ±9999), or the sun doesn't cross the twilight's angle on the given day
+///  - `Some((dawn,dusk))` otherwise
+pub fn twilight_times(
+    date: Date,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    kind: Twilight,
+) -> Option<(Timestamp, Timestamp)> {
+    sun_times_with_angle(date, latitude, longitude, elevation, kind.angle())
+}
+
+/// Calculates the approximate times at which the sun's centre crosses a given angle above or below the horizon
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the times, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+/// * `horizon_angle` - The altitude of the sun's centre at which the times are calculated. Expressed as degrees, negative values being below the horizon
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in jiff (outside of the years ±9999), or the sun doesn't cross the given angle on the given day
+///  - `Some((rise,set))` otherwise
+pub fn sun_times_with_angle(
+    date: Date,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    horizon_angle: f64,
+) -> Option<(Timestamp, Timestamp)> {
+    match generic::crossing_within_day(
+        JulianDate::from_jiff_date(date)?,
+        latitude,
+        longitude,
+        elevation,
+        horizon_angle,
+    )? {
+        Crossing::At(rise, set) => Some((rise.to_timestamp()?, set.to_timestamp()?)),
+        Crossing::AlwaysAbove | Crossing::AlwaysBelow => None,
+    }
+}
+
+/// Calculates the approximate time of solar noon (when the sun crosses the meridian, and is at its highest) at a given longitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate solar noon, in UTC
+/// * `longitude` - The longitude at which to calculate solar noon. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in jiff (outside of the years ±9999)
+///  - `Some(solar_noon)` otherwise
+pub fn solar_noon(date: Date, longitude: f64) -> Option<Timestamp> {
+    generic::SunriseEquation::new(JulianDate::from_jiff_date(date)?, longitude)
+        .solar_transit()
+        .to_timestamp()
+}
+
+/// Calculates the altitude (angle from the horizon) of the sun at a given place and moment, in degrees
+///
+/// # Arguments
+///
+/// * `timestamp` - The moment at which to calculate the altitude
+/// * `latitude` - The latitude at which to calculate the altitude. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the altitude. Expressed as degrees
+pub fn altitude(timestamp: Timestamp, latitude: f64, longitude: f64) -> f64 {
+    sun_position(timestamp, latitude, longitude).altitude
+}
+
+/// Calculates the azimuth (compass bearing) of the sun at a given place and moment, in degrees clockwise from north
+///
+/// # Arguments
+///
+/// * `timestamp` - The moment at which to calculate the azimuth
+/// * `latitude` - The latitude at which to calculate the azimuth. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the azimuth. Expressed as degrees
+pub fn azimuth(timestamp: Timestamp, latitude: f64, longitude: f64) -> f64 {
+    sun_position(timestamp, latitude, longitude).azimuth
+}
+
+/// Calculates both the altitude and azimuth of the sun at a given place and moment
+///
+/// # Arguments
+///
+/// * `timestamp` - The moment at which to calculate the sun's position
+/// * `latitude` - The latitude at which to calculate the sun's position. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sun's position. Expressed as degrees
+pub fn sun_position(timestamp: Timestamp, latitude: f64, longitude: f64) -> SunPosition {
+    generic::position(JulianDate::from(timestamp), latitude, longitude)
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use ::jiff::civil::date;
+    use ::jiff::tz::{offset, TimeZone};
+    use ::jiff::Timestamp;
+    use chrono::{FixedOffset, NaiveDate, TimeZone as _, Utc};
+
+    #[test]
+    fn matches_chrono_calculations() {
+        for day in 1..=30 {
+            let date = date(2022, 6, day);
+            let chrono_date = NaiveDate::from_ymd_opt(2022, 6, day as u32).unwrap();
+
+            let (rise, set) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+            let (chrono_rise, chrono_set) =
+                crate::sun_times(chrono_date, 53.38, -1.48, 100.0).unwrap();
+            assert_eq!(rise.as_second(), chrono_rise.timestamp());
+            assert_eq!(set.as_second(), chrono_set.timestamp());
+
+            let noon = super::solar_noon(date, -1.48).unwrap();
+            let chrono_noon = crate::solar_noon(chrono_date, -1.48).unwrap();
+            assert_eq!(noon.as_second(), chrono_noon.timestamp());
+
+            let jst = TimeZone::fixed(offset(9));
+            let (rise, set) = super::sun_times_zoned(date, &jst, 35.68, 139.69, 40.0).unwrap();
+            let chrono_jst = FixedOffset::east_opt(9 * 60 * 60).unwrap();
+            let (chrono_rise, chrono_set) =
+                crate::sun_times_tz(chrono_date, &chrono_jst, 35.68, 139.69, 40.0).unwrap();
+            assert_eq!(rise.date(), date);
+            assert_eq!(rise.timestamp().as_second(), chrono_rise.timestamp());
+            assert_eq!(set.timestamp().as_second(), chrono_set.timestamp());
+        }
+
+        let timestamp = Timestamp::from_second(1654097420).unwrap();
+        let chrono_date_time = Utc.timestamp_opt(1654097420, 0).unwrap();
+        assert_eq!(
+            super::sun_position(timestamp, 53.38, -1.48),
+            crate::sun_position(chrono_date_time, 53.38, -1.48)
+        );
+    }
+}
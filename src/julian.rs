@@ -0,0 +1,106 @@
+//! Versions of the calculations over raw Julian dates
+//!
+//! These don't depend on chrono (or std), so are available in `no_std` builds without the `chrono` feature. All
+//! Julian dates are in UTC, and converting them into any other representation of time is left to the caller
+
+use crate::generic::{self, Crossing};
+use crate::{JulianDate, SunPosition, SUNRISE_SUNSET_ANGLE};
+
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude
+///
+/// This is the same calculation as `sun_times` in the crate root, see its documentation for details
+///
+/// # Arguments
+///
+/// * `julian_day` - A Julian date anywhere within the (UTC) day on which to calculate the sunset and sunrise
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some((sunrise,sunset))` otherwise, as Julian dates
+///
+/// # Examples
+///
+/// ```
+/// //Calculate the sunset and sunrise times on the 1st of June 2022 at Sheffield university's new computer science building
+/// let (sunrise, sunset) = sun_times::julian::sun_times(2459731.5, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+pub fn sun_times(
+    julian_day: f64,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(f64, f64)> {
+    match generic::crossing_within_day(
+        JulianDate(julian_day),
+        latitude,
+        longitude,
+        elevation,
+        SUNRISE_SUNSET_ANGLE,
+    )? {
+        Crossing::At(rise, set) => Some((rise.0, set.0)),
+        Crossing::AlwaysAbove | Crossing::AlwaysBelow => None,
+    }
+}
+
+/// Calculates the approximate time of solar noon at a given longitude, as a Julian date
+///
+/// # Arguments
+///
+/// * `julian_day` - A Julian date anywhere within the (UTC) day on which to calculate solar noon
+/// * `longitude` - The longitude at which to calculate solar noon. Expressed as degrees
+pub fn solar_noon(julian_day: f64, longitude: f64) -> f64 {
+    generic::SunriseEquation::new(JulianDate(julian_day), longitude)
+        .solar_transit()
+        .0
+}
+
+/// Calculates the altitude (angle from the horizon) of the sun at a given place and moment, in degrees
+///
+/// # Arguments
+///
+/// * `julian_date` - The moment at which to calculate the altitude, as a Julian date
+/// * `latitude` - The latitude at which to calculate the altitude. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the altitude. Expressed as degrees
+pub fn altitude(julian_date: f64, latitude: f64, longitude: f64) -> f64 {
+    sun_position(julian_date, latitude, longitude).altitude
+}
+
+/// Calculates both the altitude and azimuth of the sun at a given place and moment
+///
+/// # Arguments
+///
+/// * `julian_date` - The moment at which to calculate the sun's position, as a Julian date
+/// * `latitude` - The latitude at which to calculate the sun's position. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sun's position. Expressed as degrees
+pub fn sun_position(julian_date: f64, latitude: f64, longitude: f64) -> SunPosition {
+    generic::position(JulianDate(julian_date), latitude, longitude)
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    use crate::JulianDate;
+
+    #[test]
+    fn matches_chrono_calculations() {
+        const ONE_SECOND: f64 = 1.0 / 86400.0;
+
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let julian_day = JulianDate::from_date(date);
+        let (rise, set) = super::sun_times(julian_day.0, 53.38, -1.48, 0.0).unwrap();
+        let (chrono_rise, chrono_set) = crate::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        assert!((rise - JulianDate::from(chrono_rise).0).abs() < ONE_SECOND);
+        assert!((set - JulianDate::from(chrono_set).0).abs() < ONE_SECOND);
+
+        let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 15, 30, 20).unwrap();
+        let altitude = super::altitude(JulianDate::from(date_time).0, 53.38, -1.48);
+        assert!((altitude - crate::altitude(date_time, 53.38, -1.48)).abs() < 1e-6);
+    }
+}
@@ -1,55 +1,737 @@
-use chrono::{DateTime, NaiveDate, TimeZone, Timelike, Utc};
+//! Calculate sunset and sunrise times, and the position of the sun, at given latitude and longitude
+//!
+//! # Features
+//!
+//! * `std` (enabled by default) - Use the standard library. Without it this crate is `#![no_std]`, and uses `libm`
+//!   for its floating point maths
+//! * `chrono` (enabled by default) - Provide the calculations in terms of chrono's date and time types. Without it
+//!   the calculations are still available over raw Julian dates, in the [julian] module
+//! * `time` - Provide the calculations in terms of the time crate's date and time types, in the `time` module
+//! * `jiff` - Provide the calculations in terms of the jiff crate's date and time types, in the `jiff` module
+//! * `tz-lookup` - Provide `sun_times_local`, which looks up the timezone of the location. This bundles the world's
+//!   timezone boundaries, so makes the binary noticeably larger
+//! * `serde` - Derive `Serialize` and `Deserialize` for the public types
+//!
+//! # Calendars
+//!
+//! Dates are in the proleptic Gregorian calendar, as in chrono, including those before the Gregorian calendar was
+//! adopted in 1582. For historical records dated in the Julian calendar, convert the dates with [from_julian_calendar]
+//! first, and any dates calculated back with [to_julian_calendar]
+//!
+//! The [unix] module provides the calculations over Unix timestamps, for use where passing date and time types around
+//! is awkward, e.g. across the boundary between WASM and javascript
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Timelike, Utc};
+use num_traits::Float;
+
+mod body;
+#[cfg(feature = "chrono")]
+mod builder;
+pub mod generic;
+#[cfg(feature = "jiff")]
+pub mod jiff;
+pub mod julian;
+#[cfg(feature = "tz-lookup")]
+mod local;
+#[cfg(feature = "chrono")]
+mod moon;
+#[cfg(feature = "chrono")]
+mod precise;
+#[cfg(feature = "time")]
+pub mod time;
+pub mod unix;
+
+#[cfg(feature = "chrono")]
+pub use body::sun_times_on;
+pub use body::CelestialBody;
+#[cfg(feature = "chrono")]
+pub use builder::SunTimesBuilder;
+#[cfg(feature = "tz-lookup")]
+pub use local::sun_times_local;
+#[cfg(feature = "chrono")]
+pub use moon::{moon_phase, moon_times, MoonPhase, PhaseName};
+#[cfg(feature = "chrono")]
+pub use precise::sun_times_precise;
 
 const UNIX_EPOCH: JulianDate = JulianDate(2440587.5);
 const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
 const JAN_2000: JulianDate = JulianDate(2451545.0);
-const LEAP_SECONDS: JulianDate = JulianDate(0.0008);
-const OBLIQUITY_OF_THE_ECLIPTIC: f64 = 23.44;
+/// The altitude of the sun's centre at sunrise/sunset, accounting for the sun's apparent radius and atmospheric refraction
+const SUNRISE_SUNSET_ANGLE: f64 = -0.83;
+/// The part of [SUNRISE_SUNSET_ANGLE] which is due to atmospheric refraction
+#[cfg(feature = "chrono")]
+const HORIZON_REFRACTION: f64 = 0.57;
+/// The part of [SUNRISE_SUNSET_ANGLE] which is due to the sun's apparent radius
+#[cfg(feature = "chrono")]
+const SUN_SEMIDIAMETER: f64 = 0.26;
+
+/// Estimates ΔT (how far terrestrial time, which the orbital calculations use, is ahead of UTC) at a given moment
+///
+/// This uses Espenak and Meeus' polynomial fits, see <https://eclipse.gsfc.nasa.gov/SEhelp/deltatpoly2004.html>.
+/// Around 2020 this is ~71 seconds, within a few seconds of the fixed 69 seconds previously used. Outside of
+/// 1600-2050 it's increasingly rough, being hundreds of seconds out by 500BC or 2500. Beyond 2000BC and 3000 it's held
+/// at its value for those years, as the fits are meaningless so far out, and would otherwise grow to days (and then
+/// years) and move the calculated times off the requested date
+fn delta_t(julian_date: JulianDate) -> JulianDays {
+    //evaluates a polynomial in `t`, with the coefficients in increasing order of power
+    fn polynomial(t: f64, coefficients: &[f64]) -> f64 {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0.0, |sum, &coefficient| sum * t + coefficient)
+    }
+    fn long_term(year: f64) -> f64 {
+        let u = (year - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u
+    }
+
+    let year = (2000.0 + (julian_date - JAN_2000).0 / 365.25).clamp(-2000.0, 3000.0);
+    let seconds = if year < -500.0 {
+        long_term(year)
+    } else if year < 500.0 {
+        polynomial(
+            year / 100.0,
+            &[
+                10583.6,
+                -1014.41,
+                33.78311,
+                -5.952053,
+                -0.1798452,
+                0.022174192,
+                0.0090316521,
+            ],
+        )
+    } else if year < 1600.0 {
+        polynomial(
+            (year - 1000.0) / 100.0,
+            &[
+                1574.2,
+                -556.01,
+                71.23472,
+                0.319781,
+                -0.8503463,
+                -0.005050998,
+                0.0083572073,
+            ],
+        )
+    } else if year < 1700.0 {
+        polynomial(year - 1600.0, &[120.0, -0.9808, -0.01532, 1.0 / 7129.0])
+    } else if year < 1800.0 {
+        polynomial(
+            year - 1700.0,
+            &[8.83, 0.1603, -0.0059285, 0.00013336, -1.0 / 1174000.0],
+        )
+    } else if year < 1860.0 {
+        polynomial(
+            year - 1800.0,
+            &[
+                13.72,
+                -0.332447,
+                0.0068612,
+                0.0041116,
+                -0.00037436,
+                0.0000121272,
+                -0.0000001699,
+                0.000000000875,
+            ],
+        )
+    } else if year < 1900.0 {
+        polynomial(
+            year - 1860.0,
+            &[
+                7.62,
+                0.5737,
+                -0.251754,
+                0.01680668,
+                -0.0004473624,
+                1.0 / 233174.0,
+            ],
+        )
+    } else if year < 1920.0 {
+        polynomial(
+            year - 1900.0,
+            &[-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197],
+        )
+    } else if year < 1941.0 {
+        polynomial(year - 1920.0, &[21.20, 0.84493, -0.076100, 0.0020936])
+    } else if year < 1961.0 {
+        polynomial(year - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0])
+    } else if year < 1986.0 {
+        polynomial(year - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0])
+    } else if year < 2005.0 {
+        polynomial(
+            year - 2000.0,
+            &[
+                63.86,
+                0.3345,
+                -0.060374,
+                0.0017275,
+                0.000651814,
+                0.00002373599,
+            ],
+        )
+    } else if year < 2050.0 {
+        polynomial(year - 2000.0, &[62.92, 0.32217, 0.005589])
+    } else if year < 2150.0 {
+        long_term(year) - 0.5628 * (2150.0 - year)
+    } else {
+        long_term(year)
+    };
+    JulianDays(seconds / SECONDS_PER_DAY as f64)
+}
 
-#[derive(Debug, Clone, Copy)]
-struct JulianDate(f64);
+/// A moment in time expressed as a Julian date (days since noon UTC on the 1st of January 4713BC, in the Julian calendar)
+///
+/// See <https://en.wikipedia.org/wiki/Julian_day>
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::JulianDate;
+///
+/// let date_time = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+/// let julian_date = JulianDate::from_datetime(date_time);
+/// assert_eq!(julian_date.days(), 2451545.0);
+/// assert_eq!(julian_date.to_datetime(), Some(date_time));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JulianDate(f64);
 
 impl JulianDate {
-    fn ceil_days(&self) -> f64 {
-        self.0.ceil()
+    /// Creates a Julian date from a number of days since noon UTC on the 1st of January 4713BC
+    pub const fn new(days: f64) -> Self {
+        Self(days)
     }
 
-    fn to_datetime(self) -> Option<DateTime<Utc>> {
-        Utc.timestamp_opt(
-            ((self - UNIX_EPOCH).0 * SECONDS_PER_DAY as f64).round() as i64,
-            0,
-        )
-        .single()
+    /// The number of days since noon UTC on the 1st of January 4713BC
+    pub const fn days(self) -> f64 {
+        self.0
+    }
+
+    /// The midnight at the start of the (UTC) day containing this date
+    fn start_of_day(self) -> Self {
+        Self(Float::floor(self.0 - 0.5) + 0.5)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl JulianDate {
+    /// The midnight at the start of the given (UTC) date
+    fn from_date(date: NaiveDate) -> Self {
+        //the 1st of January 1AD is day 1 from the common era, and Julian date 1721425.5
+        Self(1721424.5 + date.num_days_from_ce() as f64)
+    }
+
+    /// Converts a chrono date and time into a Julian date
+    pub fn from_datetime(date_time: DateTime<Utc>) -> Self {
+        Self((date_time.timestamp() as f64 / SECONDS_PER_DAY as f64) + UNIX_EPOCH.0)
+    }
+
+    /// Converts this Julian date into a chrono date and time, rounded to the nearest second
+    ///
+    /// Like Unix time, Julian dates here have 86400 seconds in every day, so leap seconds are skipped over rather than
+    /// given as 23:59:60, and a date on a day with a leap second converts in the same way as on any other day
+    ///
+    /// Returns `None` if the date is NaN, or not representable in chrono (~5M years from now)
+    pub fn to_datetime(self) -> Option<DateTime<Utc>> {
+        self.to_datetime_rounded(Rounding::Nearest, Duration::seconds(1))
+    }
+
+    /// Converts this Julian date into a chrono date and time, rounded to a multiple of `precision` since the Unix
+    /// epoch, e.g. to the minute
+    ///
+    /// `precision` is taken as a whole number of seconds, and at least one
+    ///
+    /// Returns `None` if the date is NaN, or not representable in chrono (~5M years from now)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use sun_times::{JulianDate, Rounding};
+    ///
+    /// let date_time = JulianDate::new(2459732.0 + 20.0 / 86400.0);
+    /// let rounded = date_time.to_datetime_rounded(Rounding::Up, Duration::minutes(1));
+    /// assert_eq!(rounded, Utc.with_ymd_and_hms(2022, 6, 1, 12, 1, 0).single());
+    /// ```
+    pub fn to_datetime_rounded(
+        self,
+        rounding: Rounding,
+        precision: Duration,
+    ) -> Option<DateTime<Utc>> {
+        //casting NaN to an integer gives 0, which would be the Unix epoch
+        if self.0.is_nan() {
+            return None;
+        }
+        let precision = precision.num_seconds().max(1);
+        let units = (self - UNIX_EPOCH).0 * SECONDS_PER_DAY as f64 / precision as f64;
+        let units = match rounding {
+            Rounding::Nearest => Float::round(units),
+            Rounding::Down => Float::floor(units),
+            Rounding::Up => Float::ceil(units),
+        };
+        //a Unix timestamp is never ambiguous in UTC (there are no leap seconds in it), so this is only `None` outside of
+        //chrono's range
+        Utc.timestamp_opt((units as i64).checked_mul(precision)?, 0)
+            .single()
+    }
+}
+
+/// The Julian day number (counting from noon on the 1st of January 4713BC) of the 1st of January 1BC in the proleptic
+/// Gregorian calendar, i.e. one less than that of chrono's day 1 of the common era
+#[cfg(feature = "chrono")]
+const CE_JULIAN_DAY_NUMBER: i64 = 1721425;
+
+/// Converts a date in the Julian calendar, as used before 1582 (and later in much of the world), into chrono's
+/// proleptic Gregorian calendar
+///
+/// The Julian calendar has a leap day every 4 years, so it is 10 days behind the Gregorian calendar in the 16th
+/// century, and 13 behind today. The date is given as its parts rather than as a `NaiveDate`, as e.g. the 29th of
+/// February 1500 is in the Julian calendar but not the Gregorian one
+///
+/// # Arguments
+///
+/// * `year` - The year of the date, numbered astronomically, i.e. 1BC is 0, 2BC is -1, etc.
+/// * `month` - The month of the date, from 1 to 12
+/// * `day` - The day of the month
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date doesn't exist in the Julian calendar, or is not representable in chrono
+///  - `Some(date)` otherwise, being the same day in the proleptic Gregorian calendar
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{from_julian_calendar, sun_times};
+///
+/// //Calculate the sun times in Rome on the last day of the Julian calendar there
+/// let date = from_julian_calendar(1582, 10, 4).unwrap();
+/// assert_eq!(date, NaiveDate::from_ymd_opt(1582, 10, 14).unwrap());
+/// let (sunrise, sunset) = sun_times(date, 41.9, 12.5, 20.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn from_julian_calendar(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    let days_in_month = match month {
+        2 if year.rem_euclid(4) == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
     }
+
+    //see https://en.wikipedia.org/wiki/Julian_day#Converting_Julian_calendar_date_to_Julian_day_number, which
+    //counts years from March so that the leap day is at the end
+    let march_years = i64::from(month <= 2);
+    let year = i64::from(year) + 4800 - march_years;
+    let month = i64::from(month) + 12 * march_years - 3;
+    let julian_day_number =
+        i64::from(day) + (153 * month + 2) / 5 + 365 * year + year.div_euclid(4) - 32083;
+    NaiveDate::from_num_days_from_ce_opt(
+        (julian_day_number - CE_JULIAN_DAY_NUMBER).try_into().ok()?,
+    )
+}
+
+/// Converts a date in chrono's proleptic Gregorian calendar into the Julian calendar, the inverse of
+/// [from_julian_calendar]
+///
+/// # Arguments
+///
+/// * `date` - The date to convert
+///
+/// # Return value
+///
+/// Returns the year (numbered astronomically, i.e. 1BC is 0), month, and day of the date in the Julian calendar
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::to_julian_calendar;
+///
+/// let date = NaiveDate::from_ymd_opt(1582, 10, 15).unwrap();
+/// assert_eq!(to_julian_calendar(date), (1582, 10, 5));
+/// ```
+#[cfg(feature = "chrono")]
+pub fn to_julian_calendar(date: NaiveDate) -> (i32, u32, u32) {
+    //see https://en.wikipedia.org/wiki/Julian_day#Julian_calendar_from_Julian_day_number
+    let julian_day_number = i64::from(date.num_days_from_ce()) + CE_JULIAN_DAY_NUMBER;
+    let c = julian_day_number + 32082;
+    let d = (4 * c + 3).div_euclid(1461);
+    let e = c - (1461 * d).div_euclid(4);
+    let m = (5 * e + 2) / 153;
+    let day = e - (153 * m + 2) / 5 + 1;
+    let month = m + 3 - 12 * (m / 10);
+    let year = d - 4800 + m / 10;
+    (year as i32, month as u32, day as u32)
 }
 
+#[cfg(feature = "chrono")]
 impl From<DateTime<Utc>> for JulianDate {
-    fn from(date: DateTime<Utc>) -> Self {
-        Self((date.timestamp() as f64 / SECONDS_PER_DAY as f64) + UNIX_EPOCH.0)
+    fn from(date_time: DateTime<Utc>) -> Self {
+        Self::from_datetime(date_time)
     }
 }
 
-impl std::ops::Sub<JulianDate> for JulianDate {
-    type Output = Self;
+#[cfg(feature = "time")]
+impl JulianDate {
+    /// The midnight at the start of the given (UTC) date
+    fn from_time_date(date: ::time::Date) -> Self {
+        //time's Julian day number counts from noon, rather than midnight
+        Self(date.to_julian_day() as f64 - 0.5)
+    }
+
+    /// Converts a time `OffsetDateTime` into a Julian date
+    pub fn from_offset_datetime(date_time: ::time::OffsetDateTime) -> Self {
+        Self((date_time.unix_timestamp() as f64 / SECONDS_PER_DAY as f64) + UNIX_EPOCH.0)
+    }
+
+    /// Converts this Julian date into a time `OffsetDateTime` in UTC, rounded to the nearest second
+    ///
+    /// Returns `None` if the date is NaN, or not representable in time (outside of the years ±9999)
+    pub fn to_offset_datetime(self) -> Option<::time::OffsetDateTime> {
+        //casting NaN to an integer gives 0, which would be the Unix epoch
+        if self.0.is_nan() {
+            return None;
+        }
+        ::time::OffsetDateTime::from_unix_timestamp(Float::round(
+            (self - UNIX_EPOCH).0 * SECONDS_PER_DAY as f64,
+        ) as i64)
+        .ok()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for JulianDate {
+    fn from(date_time: ::time::OffsetDateTime) -> Self {
+        Self::from_offset_datetime(date_time)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl JulianDate {
+    /// The midnight at the start of the given (UTC) date
+    fn from_jiff_date(date: ::jiff::civil::Date) -> Option<Self> {
+        let midnight = ::jiff::tz::Offset::UTC
+            .to_timestamp(date.to_datetime(::jiff::civil::Time::midnight()))
+            .ok()?;
+        Some(Self::from_timestamp(midnight))
+    }
+
+    /// Converts a jiff `Timestamp` into a Julian date
+    pub fn from_timestamp(timestamp: ::jiff::Timestamp) -> Self {
+        Self((timestamp.as_second() as f64 / SECONDS_PER_DAY as f64) + UNIX_EPOCH.0)
+    }
+
+    /// Converts this Julian date into a jiff `Timestamp`, rounded to the nearest second
+    ///
+    /// Returns `None` if the date is NaN, or not representable in jiff (outside of the years ±9999)
+    pub fn to_timestamp(self) -> Option<::jiff::Timestamp> {
+        if self.0.is_nan() {
+            return None;
+        }
+        ::jiff::Timestamp::from_second(
+            Float::round((self - UNIX_EPOCH).0 * SECONDS_PER_DAY as f64) as i64
+        )
+        .ok()
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl From<::jiff::Timestamp> for JulianDate {
+    fn from(timestamp: ::jiff::Timestamp) -> Self {
+        Self::from_timestamp(timestamp)
+    }
+}
+
+/// A length of time expressed as a number of days, e.g. the difference between two [JulianDate]s
+///
+/// # Examples
+///
+/// ```
+/// use sun_times::{JulianDate, JulianDays};
+///
+/// let noon = JulianDate::new(2451545.0);
+/// let midnight = noon + JulianDays::new(0.5);
+/// assert_eq!(midnight - noon, JulianDays::new(0.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JulianDays(f64);
+
+impl JulianDays {
+    /// Creates a length of time from a number of days
+    pub const fn new(days: f64) -> Self {
+        Self(days)
+    }
+
+    /// The number of days
+    pub const fn days(self) -> f64 {
+        self.0
+    }
+
+    fn ceil_days(&self) -> f64 {
+        Float::ceil(self.0)
+    }
+}
+
+impl core::ops::Sub<JulianDate> for JulianDate {
+    type Output = JulianDays;
 
     fn sub(self, rhs: JulianDate) -> Self::Output {
+        JulianDays(self.0 - rhs.0)
+    }
+}
+
+impl core::ops::Add<JulianDays> for JulianDate {
+    type Output = Self;
+
+    fn add(self, rhs: JulianDays) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl core::ops::Sub<JulianDays> for JulianDate {
+    type Output = Self;
+
+    fn sub(self, rhs: JulianDays) -> Self::Output {
         Self(self.0 - rhs.0)
     }
 }
 
-impl std::ops::Add<JulianDate> for JulianDate {
+impl core::ops::Add<JulianDays> for JulianDays {
     type Output = Self;
 
-    fn add(self, rhs: JulianDate) -> Self::Output {
+    fn add(self, rhs: JulianDays) -> Self::Output {
         Self(self.0 + rhs.0)
     }
 }
 
+impl core::ops::Sub<JulianDays> for JulianDays {
+    type Output = Self;
+
+    fn sub(self, rhs: JulianDays) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+/// The kinds of twilight, each defined by how far the sun's centre is below the horizon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Twilight {
+    /// The sun is 6° below the horizon
+    Civil,
+    /// The sun is 12° below the horizon
+    Nautical,
+    /// The sun is 18° below the horizon
+    Astronomical,
+}
+
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+impl Twilight {
+    /// The altitude of the sun's centre which marks the boundary of this twilight, expressed as degrees
+    pub(crate) fn angle(self) -> f64 {
+        match self {
+            Twilight::Civil => -6.0,
+            Twilight::Nautical => -12.0,
+            Twilight::Astronomical => -18.0,
+        }
+    }
+}
+
+/// Which part of the sun's disc marks sunrise and sunset, as it crosses the horizon
+///
+/// Sunrise and sunset are conventionally when the upper limb (top edge) of the sun crosses the horizon, as in
+/// [sun_times], but some sources use its centre, and the lower limb marks when the sun has fully risen or starts to set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SunEdge {
+    /// The top of the sun's disc, which is the first to rise and the last to set
+    #[default]
+    UpperLimb,
+    /// The centre of the sun's disc
+    Center,
+    /// The bottom of the sun's disc, which is the last to rise and the first to set
+    LowerLimb,
+}
+
+#[cfg(feature = "chrono")]
+impl SunEdge {
+    /// How much higher the sun's centre is when this edge is on the horizon than when the upper limb is, expressed as
+    /// degrees
+    pub(crate) fn angle_offset(self) -> f64 {
+        match self {
+            SunEdge::UpperLimb => 0.0,
+            SunEdge::Center => SUN_SEMIDIAMETER,
+            SunEdge::LowerLimb => 2.0 * SUN_SEMIDIAMETER,
+        }
+    }
+}
+
+/// How times are rounded to a whole number of seconds (or minutes, etc.), see [JulianDate::to_datetime_rounded]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    /// To the nearest time, as [sun_times] does
+    #[default]
+    Nearest,
+    /// To the time at or before, e.g. so that a window isn't missed by opening too late
+    Down,
+    /// To the time at or after, e.g. so that a window isn't missed by closing too early
+    Up,
+}
+
+/// The times of sunrise and sunset on a given day
+///
+/// This can be converted to and from the `(sunrise, sunset)` tuple returned by [sun_times]
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{sun_times, SunTimes};
+///
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let times = sun_times(date, 53.38, -1.48, 100.0).map(SunTimes::from).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", times.sunrise, times.sunset);
+/// ```
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunTimes {
+    pub sunrise: DateTime<Utc>,
+    pub sunset: DateTime<Utc>,
+}
+
+#[cfg(feature = "chrono")]
+impl From<(DateTime<Utc>, DateTime<Utc>)> for SunTimes {
+    fn from((sunrise, sunset): (DateTime<Utc>, DateTime<Utc>)) -> Self {
+        Self { sunrise, sunset }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<SunTimes> for (DateTime<Utc>, DateTime<Utc>) {
+    fn from(times: SunTimes) -> Self {
+        (times.sunrise, times.sunset)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl SunTimes {
+    /// Formats the sunrise and sunset as local times in a given timezone, e.g. `sunrise 06:23, sunset 17:23`
+    ///
+    /// # Arguments
+    ///
+    /// * `tz` - The timezone in which to show the times
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{FixedOffset, NaiveDate};
+    /// use sun_times::{sun_times, SunTimes};
+    ///
+    /// //Show the sunset and sunrise times at Sheffield university's new computer science building in British Summer Time
+    /// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+    /// let times = sun_times(date, 53.38, -1.48, 100.0).map(SunTimes::from).unwrap();
+    /// let bst = FixedOffset::east_opt(3600).unwrap();
+    /// println!("{}", times.format_local(&bst));
+    /// ```
+    pub fn format_local<Tz: TimeZone>(&self, tz: &Tz) -> LocalSunTimes<Tz> {
+        LocalSunTimes {
+            sunrise: self.sunrise.with_timezone(tz),
+            sunset: self.sunset.with_timezone(tz),
+        }
+    }
+
+    /// Calculates how much later the sunrise and sunset are than another's, e.g. at another location
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The sun times to compare against
+    ///
+    /// # Return value
+    ///
+    /// Returns `(sunrise, sunset)`, being how long after `other`'s sunrise and sunset this sunrise and sunset are.
+    /// These are negative if this sunrise or sunset is before the other's
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use sun_times::{sun_times, SunTimes};
+    ///
+    /// //Compare the sunset in Los Angeles with that in New York
+    /// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+    /// let new_york = sun_times(date, 40.71, -74.01, 10.0).map(SunTimes::from).unwrap();
+    /// let los_angeles = sun_times(date, 34.05, -118.24, 90.0).map(SunTimes::from).unwrap();
+    /// let (_, sunset) = los_angeles.difference(&new_york);
+    /// println!("The sun sets {} hours later in Los Angeles", sunset.num_hours());
+    /// ```
+    pub fn difference(&self, other: &SunTimes) -> (Duration, Duration) {
+        (self.sunrise - other.sunrise, self.sunset - other.sunset)
+    }
+}
+
+/// Shows the sunrise and sunset in UTC, e.g. `sunrise 06:23, sunset 17:23`. Use [SunTimes::format_local] to show them
+/// in another timezone
+#[cfg(feature = "chrono")]
+impl core::fmt::Display for SunTimes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.format_local(&Utc).fmt(f)
+    }
+}
+
+/// The times of sunrise and sunset in a given timezone, for display, see [SunTimes::format_local]
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LocalSunTimes<Tz: TimeZone> {
+    pub sunrise: DateTime<Tz>,
+    pub sunset: DateTime<Tz>,
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> core::fmt::Display for LocalSunTimes<Tz> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        //written out rather than with chrono's formatting, which needs an allocator
+        let (sunrise, sunset) = (&self.sunrise, &self.sunset);
+        write!(
+            f,
+            "sunrise {:02}:{:02}, sunset {:02}:{:02}",
+            sunrise.hour(),
+            sunrise.minute(),
+            sunset.hour(),
+            sunset.minute()
+        )
+    }
+}
+
 /// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude
 ///
 /// Note that elevation is used to correct for atmospheric refraction, so negative elevations are treated as being at
 /// sea level due to having minimal difference in refraction to being at sea level
 ///
+/// The dip of the horizon grows with the square root of the elevation, which holds for heights that are small compared
+/// to the earth's radius, e.g. mountaintops and aircraft. Beyond that the sun is never treated as further than
+/// straight down below the horizon, so from far enough up it never sets. Near the arctic circles the lower horizon means
+/// that a mountaintop can have polar day when there's a sunrise and sunset at sea level
+///
+/// The returned times fall on the requested UTC date. Far from Greenwich (beyond ~±60° of longitude) the local day
+/// straddles two UTC dates, so the sunset returned may be before the sunrise, being the end of the previous local day.
+/// Use [sun_times_tz] to get the times for a local date instead. The only exception is when the sunrise or sunset
+/// drifts across midnight, skipping a UTC date entirely, in which case the one a few minutes either side of it is used
+///
 /// # Arguments
 ///
 /// * `date` - The date on which to calculate the sunset and sunrise, in UTC
@@ -66,156 +748,5999 @@ impl std::ops::Add<JulianDate> for JulianDate {
 /// # Examples
 ///
 /// ```
+/// use chrono::Utc;
+/// use sun_times::sun_times;
+///
 /// //Calculate the sunset and sunrise times today at Sheffield university's new computer science building
-/// let times = sun_times(Utc::today(),53.38,-1.48,100.0);
-/// println!("Sunrise: {}, Sunset: {}",times.0,times.1);
+/// let (sunrise, sunset) = sun_times(Utc::now().date_naive(), 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
 /// ```
+#[cfg(feature = "chrono")]
 pub fn sun_times(
     date: NaiveDate,
     latitude: f64,
     longitude: f64,
     elevation: f64,
 ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
-    //see https://en.wikipedia.org/wiki/Sunrise_equation
-
-    const ARGUMENT_OF_PERIHELION: f64 = 102.9372;
-
-    let julian_date = JulianDate::from(
-        date.and_hms_opt(0, 0, 0)?
-            .and_local_timezone(Utc)
-            .single()?,
-    );
-
-    //elevations below sea level will have minimal atmospheric refraction + the
-    //calculation is broken below sea level, so treat negative elevations as being at sea level
-    let elevation = elevation.max(0.0);
-    let elevation_correction = -2.076 * (elevation.sqrt()) / 60.0;
-
-    let days_since_2000 = (julian_date - JAN_2000 + LEAP_SECONDS).ceil_days();
-
-    let mean_solar_time = days_since_2000 - (longitude / 360.0);
-    let solar_mean_anomaly = (357.5291 + 0.98560028 * mean_solar_time).rem_euclid(360.0);
-    let center = 1.9148 * solar_mean_anomaly.to_radians().sin()
-        + 0.0200 * (2.0 * solar_mean_anomaly).to_radians().sin()
-        + 0.0003 * (3.0 * solar_mean_anomaly).to_radians().sin();
-    let ecliptic_longitude =
-        (solar_mean_anomaly + center + 180.0 + ARGUMENT_OF_PERIHELION).rem_euclid(360.0);
-
-    let declination = (ecliptic_longitude.to_radians().sin()
-        * OBLIQUITY_OF_THE_ECLIPTIC.to_radians().sin())
-    .asin();
-    let event_hour_angle = (((-0.83 + elevation_correction).to_radians().sin()
-        - (latitude.to_radians().sin() * declination.sin()))
-        / (latitude.to_radians().cos() * declination.cos()))
-    .acos()
-    .to_degrees();
-
-    if event_hour_angle.is_nan() {
-        return None;
-    }
-
-    let solar_transit =
-        JAN_2000.0 + mean_solar_time + 0.0053 * solar_mean_anomaly.to_radians().sin()
-            - 0.0069 * (2.0 * ecliptic_longitude).to_radians().sin();
-    let solar_transit_julian = JulianDate(solar_transit);
+    sun_times_with_angle(date, latitude, longitude, elevation, SUNRISE_SUNSET_ANGLE)
+}
 
-    let julian_rise = JulianDate(solar_transit_julian.0 - event_hour_angle / 360.0);
-    let julian_set = JulianDate(solar_transit_julian.0 + event_hour_angle / 360.0);
-    let rise = julian_rise.to_datetime();
-    let set = julian_set.to_datetime();
-    if let (Some(rise), Some(set)) = (rise, set) {
-        Some((rise, set))
-    } else {
-        None
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude, with the latitude and longitude expressed as radians
+///
+/// This is [sun_times] for callers already working in radians, which skips converting the latitude and longitude.
+/// Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as radians
+/// * `longitude` - The longitude at which to calculate the times. Expressed as radians
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_rad;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_rad(date, 0.9317, -0.0258, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_rad(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let horizon = generic::Horizon::from_radians(latitude, elevation, SUNRISE_SUNSET_ANGLE);
+    let crossing = generic::pick_within_day(JulianDate::from_date(date), |day| {
+        generic::SunriseEquation::new_radians(day, longitude).crossing_horizon(&horizon)
+    })?;
+    match crossing {
+        generic::Crossing::At(rise, set) => Some((rise.to_datetime()?, set.to_datetime()?)),
+        generic::Crossing::AlwaysAbove | generic::Crossing::AlwaysBelow => None,
     }
 }
 
-/// Calculates the altitude (angle from the horizon) of the sun at a given place and moment
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude, as Julian dates
+///
+/// This is the same as [sun_times], but skips converting the times into chrono's types, which rounds them to the
+/// nearest second and fails for dates chrono can't represent. See also [julian::sun_times], which takes the date as a
+/// Julian date too
+///
 /// # Arguments
 ///
-/// * `date_time` - The date and time on which to calculate the sunset and sunrise
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
 /// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
 /// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
 ///
 /// # Return value
 ///
-/// Returns the altitude in degrees
+/// Returns
+///  - `None` if sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some((sunrise,sunset))` otherwise
 ///
-/// # Notes
+/// # Examples
 ///
-/// This currently does not include an altitude correction like the [sun_times] function does, but *is* usable within arctic regions
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_julian;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_julian(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise.days(), sunset.days());
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_julian(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(JulianDate, JulianDate)> {
+    match generic::crossing_within_day(
+        JulianDate::from_date(date),
+        latitude,
+        longitude,
+        elevation,
+        SUNRISE_SUNSET_ANGLE,
+    )? {
+        generic::Crossing::At(rise, set) => Some((rise, set)),
+        generic::Crossing::AlwaysAbove | generic::Crossing::AlwaysBelow => None,
+    }
+}
+
+/// Calculates the approximate sunset and sunrise times for every date in a range, at a given latitude, longitude, and altitude
+///
+/// This is equivalent to calling [sun_times] for each date from `start` to `end` (inclusive)
+///
+/// # Arguments
+///
+/// * `start` - The first date on which to calculate the sunset and sunrise, in UTC
+/// * `end` - The last date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns an iterator over each date in the range, along with the result of [sun_times] for that date
 ///
 /// # Examples
 ///
 /// ```
-/// //Calculate the sunset and sunrise times today at Sheffield university's new computer science building
-/// let altitude = altitude(Utc::now(),53.38,-1.48);
-/// println!("Altitude: {}",altitude);
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_range;
+///
+/// //List the days without a sunrise/sunset in 2022, in Tromsø
+/// let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+/// for (date, times) in sun_times_range(start, end, 69.65, 18.96, 0.0) {
+///     if times.is_none() {
+///         println!("No sunrise/sunset on {}", date);
+///     }
+/// }
 /// ```
-pub fn altitude(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
-    //see https://en.wikipedia.org/wiki/Sunrise_equation
-    //see https://en.wikipedia.org/wiki/Astronomical_coordinate_systems
-    //see http://www.stargazing.net/kepler/altaz.html
-
-    const ARGUMENT_OF_PERIHELION: f64 = 102.9372;
-
-    let julian_date = JulianDate::from(date_time);
-
-    let days_since_2000 = (julian_date - JAN_2000 + LEAP_SECONDS).ceil_days();
-
-    let mean_solar_time = days_since_2000 - (longitude / 360.0);
-    let solar_mean_anomaly = (357.5291 + 0.98560028 * mean_solar_time).rem_euclid(360.0);
-    let center = 1.9148 * solar_mean_anomaly.to_radians().sin()
-        + 0.0200 * (2.0 * solar_mean_anomaly).to_radians().sin()
-        + 0.0003 * (3.0 * solar_mean_anomaly).to_radians().sin();
-    let ecliptic_longitude =
-        (solar_mean_anomaly + center + 180.0 + ARGUMENT_OF_PERIHELION).rem_euclid(360.0);
-
-    let sin_declination =
-        ecliptic_longitude.to_radians().sin() * OBLIQUITY_OF_THE_ECLIPTIC.to_radians().sin();
-    let declination = sin_declination.asin();
+#[cfg(feature = "chrono")]
+#[allow(clippy::type_complexity)]
+pub fn sun_times_range(
+    start: NaiveDate,
+    end: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> impl Iterator<Item = (NaiveDate, Option<(DateTime<Utc>, DateTime<Utc>)>)> {
+    start
+        .iter_days()
+        .take_while(move |&date| date <= end)
+        .map(move |date| (date, sun_times(date, latitude, longitude, elevation)))
+}
 
-    let right_ascension = (ecliptic_longitude.to_radians().sin()
-        * OBLIQUITY_OF_THE_ECLIPTIC.to_radians().cos())
-    .atan2(ecliptic_longitude.to_radians().cos())
-    .to_degrees();
+/// Calculates the approximate sunset and sunrise times for every day of a month, at a given latitude, longitude, and altitude
+///
+/// # Arguments
+///
+/// * `year` - The year containing the month
+/// * `month` - The month on which to calculate the sunsets and sunrises, from 1 (January) to 12 (December)
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns the result of [sun_times] for each day of the month, indexed by the day of the month minus one. This is
+/// empty if the month is invalid or not representable in chrono
+///
+/// # Examples
+///
+/// ```
+/// use sun_times::sun_times_month;
+///
+/// //Calculate the sunrise and sunset times for June 2022 at Sheffield university's new computer science building
+/// let times = sun_times_month(2022, 6, 53.38, -1.48, 100.0);
+/// let (sunrise, sunset) = times[20].unwrap();
+/// println!("Sunrise on the 21st: {}, Sunset on the 21st: {}", sunrise, sunset);
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+#[allow(clippy::type_complexity)]
+pub fn sun_times_month(
+    year: i32,
+    month: u32,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Vec<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+    let Some(start) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return Vec::new();
+    };
+    start
+        .iter_days()
+        .take_while(|date| date.month() == month)
+        .map(|date| sun_times(date, latitude, longitude, elevation))
+        .collect()
+}
+
+/// Calculates the approximate sunset and sunrise times on a given date in a given timezone, at a given latitude, longitude, and altitude
+///
+/// Unlike [sun_times], the date is interpreted in the given timezone, so the returned times are the sunrise and sunset
+/// which fall on that date locally. Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in `tz`
+/// * `tz` - The timezone in which `date` is interpreted, and in which the times are returned
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or the sun does not both rise and set on that date in `tz`
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{FixedOffset, NaiveDate};
+/// use sun_times::sun_times_tz;
+///
+/// //Calculate the sunset and sunrise times in Tokyo, in Japan Standard Time
+/// let jst = FixedOffset::east_opt(9 * 60 * 60).unwrap();
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_tz(date, &jst, 35.68, 139.69, 40.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_tz<Tz: TimeZone>(
+    date: NaiveDate,
+    tz: &Tz,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
+    times_on_local_date(date, tz, |date| {
+        sun_times(date, latitude, longitude, elevation)
+    })
+}
+
+/// Calculates the approximate sunset and sunrise times on a given date in the local mean time of a standard meridian
+///
+/// Local mean time is the mean solar time at the standard meridian, which is ahead of UTC by 4 minutes for every
+/// degree east of Greenwich. This is the convention of old almanacs and of railway time before time zones, where the
+/// times at places away from the meridian differ from those at the meridian by their difference in longitude. The
+/// date is interpreted in, and the times are returned in, that local mean time, as in [sun_times_tz]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in local mean time
+/// * `standard_meridian` - The longitude whose mean time the times are given in. Expressed as degrees
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the standard meridian is outside of [-180, 180] degrees, the date is not representable in chrono (~5M years from now), or the sun does not both rise and set on that date in local mean time
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_local_mean_time;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building, in Sheffield's own local mean time
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_local_mean_time(date, -1.48, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_local_mean_time(
+    date: NaiveDate,
+    standard_meridian: f64,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+    if !(-180.0..=180.0).contains(&standard_meridian) {
+        return None;
+    }
+    //4 minutes, i.e. 240 seconds, per degree
+    let offset = FixedOffset::east_opt((standard_meridian * 240.0).round() as i32)?;
+    sun_times_tz(date, &offset, latitude, longitude, elevation)
+}
+
+/// Converts a time into the number of hours past midnight in a given timezone, e.g. 6.39 for 06:23:24, for plotting
+///
+/// The hours are counted on the local clock, so on days when the clocks change they jump forward or back with them
+///
+/// # Arguments
+///
+/// * `time` - The time to convert
+/// * `tz` - The timezone whose midnight the hours are counted from
+///
+/// # Return value
+///
+/// Returns the hours, in the range [0, 24)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{NaiveDate, Utc};
+/// use sun_times::{as_fractional_hours, sun_times};
+///
+/// //Plot the day at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times(date, 53.38, -1.48, 100.0).unwrap();
+/// let (from, to) = (as_fractional_hours(sunrise, &Utc), as_fractional_hours(sunset, &Utc));
+/// println!("Sun up from {:.2}h to {:.2}h", from, to);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn as_fractional_hours<Tz: TimeZone>(time: DateTime<Utc>, tz: &Tz) -> f64 {
+    let time = time.with_timezone(tz).time();
+    let seconds = f64::from(time.num_seconds_from_midnight()) + f64::from(time.nanosecond()) / 1e9;
+    seconds / 3600.0
+}
+
+/// Picks out the times which land on the given date in `tz`, from those calculated by `times` for each UTC date
+#[cfg(feature = "chrono")]
+fn times_on_local_date<Tz: TimeZone>(
+    date: NaiveDate,
+    tz: &Tz,
+    times: impl Fn(NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
+    //a local date can span parts of up to three UTC dates, so look at the times on each of them
+    //and pick out those which land on the requested date once converted into the timezone
+    let dates = [date.pred_opt()?, date, date.succ_opt()?];
+    let local_times = || {
+        dates
+            .into_iter()
+            .filter_map(&times)
+            .map(|(rise, set)| (rise.with_timezone(tz), set.with_timezone(tz)))
+    };
+    let (rise, _) = local_times().find(|(rise, _)| rise.date_naive() == date)?;
+    let (_, set) = local_times().find(|(_, set)| set.date_naive() == date)?;
+    Some((rise, set))
+}
+
+/// Calculates the approximate dawn and dusk times of the given kind of twilight at a given latitude, longitude, and altitude
+///
+/// Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the dawn and dusk, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+/// * `kind` - The kind of twilight to calculate the boundaries of
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono, or the sun never reaches the depression angle of the given twilight on that day
+///  - `Some((dawn,dusk))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{twilight_times, Twilight};
+///
+/// //Calculate the civil dawn and dusk times at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (dawn, dusk) = twilight_times(date, 53.38, -1.48, 100.0, Twilight::Civil).unwrap();
+/// println!("Civil dawn: {}, Civil dusk: {}", dawn, dusk);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn twilight_times(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    kind: Twilight,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    sun_times_with_angle(date, latitude, longitude, elevation, kind.angle())
+}
+
+/// Calculates the approximate times at which the sun's centre crosses a given angle above or below the horizon
+///
+/// This is the calculation behind [sun_times] (which uses an angle of -0.83°, accounting for the sun's apparent radius
+/// and atmospheric refraction), but can be used to find e.g. the times at which the sun's centre is on the horizon (0°),
+/// or passes behind an obstructed horizon. Elevation is corrected for in the same way as in [sun_times], in addition to the given angle
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the times, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+/// * `horizon_angle` - The altitude of the sun's centre at which the times are calculated. Expressed as degrees, negative values being below the horizon
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono, or the sun never crosses the given angle on that day
+///  - `Some((rise,set))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_with_angle;
+///
+/// //Calculate when the sun rises above and sinks below a ridge 3° above the horizon
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (rise, set) = sun_times_with_angle(date, 53.38, -1.48, 100.0, 3.0).unwrap();
+/// println!("Rise: {}, Set: {}", rise, set);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_with_angle(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    horizon_angle: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    generic::times_within_day(date, latitude, longitude, elevation, horizon_angle)
+}
+
+/// Calculates the approximate sunset and sunrise times at a given latitude and longitude, with the elevation of the terrain and the observer's eye height above it treated separately
+///
+/// [sun_times] treats its elevation as the height from which the observer looks down on a sea level horizon. Here
+/// the terrain's elevation instead thins the air, reducing the atmospheric refraction at the horizon in proportion to
+/// the air pressure, while the eye height above the terrain lowers the horizon by the usual geometric dip (corrected
+/// for refraction near the ground) of 1.76' per square root meter. So an observer on a 2000m plateau is barely
+/// affected, while one at the top of a 100m tower sees the sun earlier
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `terrain_elevation` - The elevation of the ground around the observer. Expressed as meters above sea level. Negative values will be ignored
+/// * `eye_height` - The height of the observer's eyes above the ground. Expressed as meters. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_ex;
+///
+/// //Calculate the sunset and sunrise times from the first floor of Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_ex(date, 53.38, -1.48, 100.0, 5.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_ex(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    terrain_elevation: f64,
+    eye_height: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    //the air pressure falls by a factor of e every ~8.4km (the scale height), and refraction with it
+    const SCALE_HEIGHT: f64 = 8434.0;
+    let pressure_ratio = (-terrain_elevation.max(0.0) / SCALE_HEIGHT).exp();
+    let horizon_dip = 1.76 * eye_height.max(0.0).sqrt() / 60.0;
+    let horizon_angle =
+        SUNRISE_SUNSET_ANGLE + HORIZON_REFRACTION * (1.0 - pressure_ratio) - horizon_dip;
+    sun_times_with_angle(date, latitude, longitude, 0.0, horizon_angle)
+}
+
+/// Calculates the approximate times at which the sun first rises above, and last sinks below, a given altitude at a given latitude and longitude
+///
+/// This is useful for finding the productive hours of a solar panel, e.g. while the sun is above 10°, and for schedules
+/// defined by the sun reaching a given altitude, such as Islamic prayer times (e.g. Fajr and Isha when the sun is 18°
+/// below the horizon, or Asr at the altitude from [shadow_ratio_altitude]). It's the same calculation as
+/// [sun_times_with_angle] at sea level, no correction being made for the elevation or atmospheric refraction
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the times, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `angle` - The altitude of the sun's centre above which to calculate the times. Expressed as degrees, negative values being below the horizon
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or the sun doesn't cross the given altitude on that day
+///  - `Some((start,end))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::daylight_above;
+///
+/// //Calculate when the sun is more than 10° up at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (start, end) = daylight_above(date, 53.38, -1.48, 10.0).unwrap();
+/// println!("Productive sun from {} to {}", start, end);
+///
+/// //Calculate the times of Fajr, Asr, and Isha in Mecca
+/// use sun_times::shadow_ratio_altitude;
+/// let (fajr, isha) = daylight_above(date, 21.42, 39.83, -18.0).unwrap();
+/// let (_, asr) = daylight_above(date, 21.42, 39.83, shadow_ratio_altitude(date, 21.42, 1.0)).unwrap();
+/// println!("Fajr: {}, Asr: {}, Isha: {}", fajr, asr, isha);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn daylight_above(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    angle: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    sun_times_with_angle(date, latitude, longitude, 0.0, angle)
+}
+
+/// Calculates the altitude of the sun at which a vertical object's shadow is a given multiple of its height longer
+/// than the shadow at solar noon
+///
+/// This is how the time of Asr is defined in Islamic prayer times, with a ratio of 1 (or 2 in the Hanafi school), and
+/// can be passed to [daylight_above] to find the time
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the altitude, in UTC
+/// * `latitude` - The latitude at which to calculate the altitude. Expressed as degrees
+/// * `shadow_ratio` - How many times the object's height longer the shadow is than at solar noon
+#[cfg(feature = "chrono")]
+pub fn shadow_ratio_altitude(date: NaiveDate, latitude: f64, shadow_ratio: f64) -> f64 {
+    //at solar noon the sun is as far from the zenith as the latitude is from the declination
+    let noon_zenith_angle = (latitude - declination(date)).abs().to_radians();
+    (1.0 / (shadow_ratio + noon_zenith_angle.tan()))
+        .atan()
+        .to_degrees()
+}
+
+/// Calculates how long the sun spends above each of several altitudes on a given day, e.g. for binning solar panel yield
+///
+/// This calculates the sun's position for the day once, and then the span for each altitude from the hour angle at
+/// which the sun crosses it, so is cheaper than calling [daylight_above] for each. As in [daylight_above], the
+/// altitudes are those of the sun's centre, with no correction for the elevation or atmospheric refraction
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the durations, in UTC
+/// * `latitude` - The latitude at which to calculate the durations. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the durations. Expressed as degrees
+/// * `thresholds` - The altitudes of the sun above which to calculate the durations. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns how long the sun is above each of `thresholds`, in the same order. This is a whole day if the sun stays
+/// above the threshold, and zero if it stays below it
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::time_above_thresholds;
+///
+/// //Calculate how long the sun is above 0°, 10°, 20°, and 30° at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let thresholds = [0.0, 10.0, 20.0, 30.0];
+/// for (threshold, duration) in thresholds.iter().zip(time_above_thresholds(date, 53.38, -1.48, &thresholds)) {
+///     println!("Above {}°: {} minutes", threshold, duration.num_minutes());
+/// }
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn time_above_thresholds(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    thresholds: &[f64],
+) -> Vec<Duration> {
+    let equation = generic::SunriseEquation::new(JulianDate::from_date(date), longitude);
+    thresholds
+        .iter()
+        .map(
+            |&threshold| match equation.crossing(latitude, 0.0, threshold) {
+                Some(generic::Crossing::At(rise, set)) => {
+                    Duration::seconds(((set - rise).0 * SECONDS_PER_DAY as f64).round() as i64)
+                }
+                Some(generic::Crossing::AlwaysAbove) => Duration::days(1),
+                Some(generic::Crossing::AlwaysBelow) | None => Duration::zero(),
+            },
+        )
+        .collect()
+}
+
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude, by searching for where the sun's [altitude] crosses the horizon
+///
+/// Rather than solving the sunrise equation for a single hour angle, this samples the altitude through the UTC day and
+/// refines each crossing by bisection. This is slower than [sun_times], but consistent with [altitude] everywhere,
+/// including close to the start and end of polar day/night, where the sun only just rises or sets. Elevation is
+/// handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or the sun doesn't both rise and set within the UTC day
+///  - `Some((sunrise,sunset))` otherwise, being the first sunrise and first sunset within the day
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_numerical;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_numerical(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_numerical(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    //short enough that the sun can't rise and set again between samples
+    const SAMPLES_PER_DAY: u32 = 144;
+    const PRECISION: f64 = 0.5 / SECONDS_PER_DAY as f64;
+
+    let start_of_day = JulianDate::from_date(date);
+    let threshold = sunrise_sunset_altitude(elevation);
+    //how far the sun is above the horizon, `days` after the start of the day
+    let height = |days: f64| {
+        generic::position(JulianDate(start_of_day.0 + days), latitude, longitude).altitude
+            - threshold
+    };
+
+    let mut rise = None;
+    let mut set = None;
+    for sample in 0..SAMPLES_PER_DAY {
+        let (mut before, mut after) = (
+            f64::from(sample) / f64::from(SAMPLES_PER_DAY),
+            f64::from(sample + 1) / f64::from(SAMPLES_PER_DAY),
+        );
+        let rising = height(before) < 0.0;
+        if rising == (height(after) < 0.0) {
+            continue;
+        }
+        while after - before > PRECISION {
+            let middle = (before + after) / 2.0;
+            if (height(middle) < 0.0) == rising {
+                before = middle;
+            } else {
+                after = middle;
+            }
+        }
+        let crossing = if rising { &mut rise } else { &mut set };
+        crossing.get_or_insert(JulianDate(start_of_day.0 + (before + after) / 2.0));
+        if rise.is_some() && set.is_some() {
+            break;
+        }
+    }
+    Some((rise?.to_datetime()?, set?.to_datetime()?))
+}
+
+/// Calculates the approximate times at which the sun rises above and sets below a horizon which varies with azimuth, e.g. due to surrounding terrain
+///
+/// The altitude of the horizon is treated in the same way as [SunTimesBuilder::horizon_angle], i.e. the sun rises when
+/// its centre is 0.83° below the horizon. As the horizon replaces the flat horizon seen from a given elevation, there
+/// is no elevation argument
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `horizon` - Gives the altitude of the horizon at a given azimuth, both expressed as degrees. The azimuth is
+///   clockwise from north, in the range [0, 360)
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or the sun doesn't both rise above and set below the horizon on that day
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_with_horizon;
+///
+/// //Calculate the sunset and sunrise times in a valley with hills 5° high to the east
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let horizon = |azimuth: f64| if azimuth < 180.0 { 5.0 } else { 0.0 };
+/// let (sunrise, sunset) = sun_times_with_horizon(date, 53.38, -1.48, horizon).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_with_horizon(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    horizon: impl Fn(f64) -> f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    const MAX_ITERATIONS: usize = 10;
+
+    //the sun's azimuth changes slowly compared to its altitude, so alternate between finding when the sun crosses the
+    //horizon's altitude at its previous azimuth, and finding the horizon's altitude at its new azimuth
+    let crossing = |rise: bool| {
+        let time_at = |angle| {
+            let (rise_time, set_time) =
+                sun_times_with_angle(date, latitude, longitude, 0.0, angle)?;
+            Some(if rise { rise_time } else { set_time })
+        };
+        let mut time = time_at(SUNRISE_SUNSET_ANGLE)?;
+        for _ in 0..MAX_ITERATIONS {
+            let horizon_angle = horizon(azimuth(time, latitude, longitude));
+            let next = time_at(SUNRISE_SUNSET_ANGLE + horizon_angle)?;
+            if (next - time).num_seconds().abs() < 1 {
+                return Some(next);
+            }
+            time = next;
+        }
+        Some(time)
+    };
+    Some((crossing(true)?, crossing(false)?))
+}
+
+/// A span of time between two moments
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+#[cfg(feature = "chrono")]
+impl Interval {
+    /// The length of this interval
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// Calculates the approximate morning and evening golden hours (when the sun's centre is between 4° below and 6°
+/// above the horizon) at a given latitude, longitude, and altitude
+///
+/// Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the golden hours, in UTC
+/// * `latitude` - The latitude at which to calculate the golden hours. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the golden hours. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the golden hours. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns the `(morning, evening)` golden hours, each of which is `None` if the sun does not pass through both
+/// angles on that day (e.g. near the poles, where the two golden hours can merge or vanish entirely)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::golden_hour;
+///
+/// //Calculate the golden hours at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (morning, evening) = golden_hour(date, 53.38, -1.48, 100.0);
+/// let evening = evening.unwrap();
+/// println!("Evening golden hour: {} to {}", evening.start, evening.end);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn golden_hour(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> (Option<Interval>, Option<Interval>) {
+    intervals_between_angles(date, latitude, longitude, elevation, -4.0, 6.0)
+}
+
+/// Calculates the approximate morning and evening blue hours (when the sun's centre is between 6° and 4° below the
+/// horizon) at a given latitude, longitude, and altitude
+///
+/// Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the blue hours, in UTC
+/// * `latitude` - The latitude at which to calculate the blue hours. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the blue hours. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the blue hours. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns the `(morning, evening)` blue hours, each of which is `None` if the sun does not pass through both
+/// angles on that day (e.g. near the poles, where the two blue hours can merge or vanish entirely)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::blue_hour;
+///
+/// //Calculate the blue hours at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (morning, evening) = blue_hour(date, 53.38, -1.48, 100.0);
+/// let morning = morning.unwrap();
+/// println!("Morning blue hour: {} to {}", morning.start, morning.end);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn blue_hour(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> (Option<Interval>, Option<Interval>) {
+    intervals_between_angles(date, latitude, longitude, elevation, -6.0, -4.0)
+}
+
+/// Calculates the approximate length of dawn (the time from the start of a given kind of twilight until sunrise) at a given latitude, longitude, and altitude
+///
+/// Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the length of dawn, in UTC
+/// * `latitude` - The latitude at which to calculate the length of dawn. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the length of dawn. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the length of dawn. Expressed as meters above sea level. Negative values will be ignored
+/// * `kind` - The kind of twilight with which dawn starts
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the sun doesn't both rise and reach the depression angle of the given twilight on that day, e.g.
+///    when twilight lasts all night in summer at high latitudes
+///  - `Some(duration)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{dawn_duration, Twilight};
+///
+/// //Calculate how long it's light before sunrise at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+/// let duration = dawn_duration(date, 53.38, -1.48, 100.0, Twilight::Civil).unwrap();
+/// println!("Civil dawn lasts {} minutes", duration.num_minutes());
+/// ```
+#[cfg(feature = "chrono")]
+pub fn dawn_duration(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    kind: Twilight,
+) -> Option<Duration> {
+    let (dawn, _) = intervals_between_angles(
+        date,
+        latitude,
+        longitude,
+        elevation,
+        kind.angle(),
+        SUNRISE_SUNSET_ANGLE,
+    );
+    Some(dawn?.duration())
+}
+
+/// Calculates the approximate length of dusk (the time from sunset until the end of a given kind of twilight) at a given latitude, longitude, and altitude
+///
+/// Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the length of dusk, in UTC
+/// * `latitude` - The latitude at which to calculate the length of dusk. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the length of dusk. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the length of dusk. Expressed as meters above sea level. Negative values will be ignored
+/// * `kind` - The kind of twilight with which dusk ends
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the sun doesn't both set and reach the depression angle of the given twilight on that day, e.g.
+///    when twilight lasts all night in summer at high latitudes
+///  - `Some(duration)` otherwise
+#[cfg(feature = "chrono")]
+pub fn dusk_duration(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    kind: Twilight,
+) -> Option<Duration> {
+    let (_, dusk) = intervals_between_angles(
+        date,
+        latitude,
+        longitude,
+        elevation,
+        kind.angle(),
+        SUNRISE_SUNSET_ANGLE,
+    );
+    Some(dusk?.duration())
+}
+
+/// Calculates how long before sunrise civil dawn (first light) begins, at a given latitude, longitude, and altitude,
+/// e.g. to notify a set time before first light
+///
+/// This is [dawn_duration] for civil twilight, the time the earth takes to turn between the hour angles at which the
+/// sun's centre is 6° below the horizon and at which it rises. Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the offset, in UTC
+/// * `latitude` - The latitude at which to calculate the offset. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the offset. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the offset. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the sun doesn't both rise and get 6° below the horizon on that day, e.g. at high latitudes in summer
+///  - `Some(offset)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::civil_dawn_offset;
+///
+/// //Calculate how long before sunrise it gets light at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+/// let offset = civil_dawn_offset(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("First light is {} minutes before sunrise", offset.num_minutes());
+/// ```
+#[cfg(feature = "chrono")]
+pub fn civil_dawn_offset(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<Duration> {
+    dawn_duration(date, latitude, longitude, elevation, Twilight::Civil)
+}
+
+/// Calculates how long after sunset civil dusk (last light) ends, at a given latitude, longitude, and altitude
+///
+/// This is [dusk_duration] for civil twilight, see [civil_dawn_offset]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the offset, in UTC
+/// * `latitude` - The latitude at which to calculate the offset. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the offset. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the offset. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the sun doesn't both set and get 6° below the horizon on that day, e.g. at high latitudes in summer
+///  - `Some(offset)` otherwise
+#[cfg(feature = "chrono")]
+pub fn civil_dusk_offset(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<Duration> {
+    dusk_duration(date, latitude, longitude, elevation, Twilight::Civil)
+}
+
+/// Calculates the morning and evening intervals during which the sun's centre is between the `lower` and `upper` angles
+#[cfg(feature = "chrono")]
+fn intervals_between_angles(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    lower: f64,
+    upper: f64,
+) -> (Option<Interval>, Option<Interval>) {
+    let crossings = |angle| sun_times_with_angle(date, latitude, longitude, elevation, angle);
+    match (crossings(lower), crossings(upper)) {
+        (Some((lower_rise, lower_set)), Some((upper_rise, upper_set))) => (
+            Some(Interval {
+                start: lower_rise,
+                end: upper_rise,
+            }),
+            Some(Interval {
+                start: upper_set,
+                end: lower_set,
+            }),
+        ),
+        _ => (None, None),
+    }
+}
+
+/// Whether the sun rises and sets on a given day, or stays above or below the horizon all day
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SunState {
+    /// The sun rises and sets at the given times
+    Rises {
+        rise: DateTime<Utc>,
+        set: DateTime<Utc>,
+    },
+    /// The sun stays above the horizon for the whole day (polar day)
+    AlwaysUp,
+    /// The sun stays below the horizon for the whole day (polar night)
+    AlwaysDown,
+}
+
+/// Calculates whether the sun rises and sets at a given latitude, longitude, and altitude, and if so at what times
+///
+/// This is the same calculation as [sun_times], but distinguishes between polar day and polar night rather than
+/// returning `None` for both. The rise and set are always either side of solar noon on the given date, so far from
+/// Greenwich (beyond ~±60° of longitude) one of them may fall on the previous or next UTC date
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now)
+///  - `Some(SunState::AlwaysUp)` or `Some(SunState::AlwaysDown)` during polar day or polar night respectively
+///  - `Some(SunState::Rises{rise,set})` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{sun_state, SunState};
+///
+/// //Check whether the sun sets on midsummer's day in Tromsø
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+/// let state = sun_state(date, 69.65, 18.96, 0.0).unwrap();
+/// assert_eq!(state, SunState::AlwaysUp);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_state(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<SunState> {
+    sun_state_with_angle(date, latitude, longitude, elevation, SUNRISE_SUNSET_ANGLE)
+}
+
+#[cfg(feature = "chrono")]
+fn sun_state_with_angle(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    horizon_angle: f64,
+) -> Option<SunState> {
+    generic::sun_state_with_angle(date, latitude, longitude, elevation, horizon_angle)
+}
+
+/// Checks whether the sun gets far enough below the horizon for astronomical night (18° below) on a given date
+///
+/// Astronomical twilight lasts all night around midsummer beyond ~±48.5° of latitude, so the sky never gets fully
+/// dark, as in the "white nights" of high latitudes. The sun being below 18° for the whole day, deep into polar
+/// night, counts as having an astronomical night
+///
+/// # Arguments
+///
+/// * `date` - The date on which to check, in UTC
+/// * `latitude` - The latitude at which to check. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns `true` if the sun gets more than 18° below the horizon on the given date. Returns `false` otherwise,
+/// including if the date is not representable in chrono (~5M years from now)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::has_astronomical_night;
+///
+/// //Check whether it gets fully dark at Sheffield university's new computer science building on midsummer's day
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+/// assert!(!has_astronomical_night(date, 53.38));
+/// ```
+#[cfg(feature = "chrono")]
+pub fn has_astronomical_night(date: NaiveDate, latitude: f64) -> bool {
+    match sun_state_with_angle(date, latitude, 0.0, 0.0, Twilight::Astronomical.angle()) {
+        Some(SunState::Rises { .. }) | Some(SunState::AlwaysDown) => true,
+        Some(SunState::AlwaysUp) | None => false,
+    }
+}
+
+/// Calculates the length of astronomical night (when the sun is more than 18° below the horizon) at a given latitude and longitude
+///
+/// This is the window of full darkness, from astronomical dusk to astronomical dawn, e.g. for scheduling observations.
+/// It is calculated from the dawn and dusk of [twilight_times] on the given date, so is the night after that day, or
+/// the one before it far from Greenwich where the dusk on the UTC date comes before the dawn
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the length of the night, in UTC
+/// * `latitude` - The latitude at which to calculate the length of the night. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the length of the night. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or the sun doesn't get 18° below the horizon, see [has_astronomical_night]
+///  - `Some(Duration::days(1))` if the sun stays more than 18° below the horizon all day, deep into polar night
+///  - `Some(length)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::astronomical_night_length;
+///
+/// //Calculate how long it is fully dark for at Sheffield university's new computer science building in the winter
+/// let date = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+/// let night = astronomical_night_length(date, 53.38, -1.48).unwrap();
+/// println!("Full darkness for {} hours", night.num_hours());
+/// ```
+#[cfg(feature = "chrono")]
+pub fn astronomical_night_length(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+) -> Option<Duration> {
+    match sun_state_with_angle(
+        date,
+        latitude,
+        longitude,
+        0.0,
+        Twilight::Astronomical.angle(),
+    )? {
+        SunState::Rises {
+            rise: dawn,
+            set: dusk,
+        } => {
+            //from the dusk until the next dawn, a day later if the dawn is on the same local day
+            let night = dawn - dusk;
+            if night < Duration::zero() {
+                Some(night + Duration::days(1))
+            } else {
+                Some(night)
+            }
+        }
+        SunState::AlwaysDown => Some(Duration::days(1)),
+        SunState::AlwaysUp => None,
+    }
+}
+
+/// The periods of polar day and polar night which start in a given year, see [polar_periods]
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolarPeriods {
+    /// The first and last days on which the sun doesn't set
+    pub midnight_sun: Option<(NaiveDate, NaiveDate)>,
+    /// The first and last days on which the sun doesn't rise
+    pub polar_night: Option<(NaiveDate, NaiveDate)>,
+}
+
+/// Calculates the approximate periods of polar day (midnight sun) and polar night at a given latitude
+///
+/// Each period is the one starting in the given year, so may end in the next year, as the antarctic's midnight sun and
+/// the arctic's polar night do. A period which lasts the whole year runs from the first to the last day of the year
+///
+/// # Arguments
+///
+/// * `year` - The year in which the periods start
+/// * `latitude` - The latitude at which to calculate the periods. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns the periods, each of which is `None` if there is no such period starting in the given year (e.g. within
+/// ~±67° of latitude), or the year is not representable in chrono
+///
+/// # Examples
+///
+/// ```
+/// use sun_times::polar_periods;
+///
+/// //Calculate when the sun doesn't set in Tromsø
+/// let periods = polar_periods(2022, 69.65);
+/// let (start, end) = periods.midnight_sun.unwrap();
+/// println!("Midnight sun from {} to {}", start, end);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn polar_periods(year: i32, latitude: f64) -> PolarPeriods {
+    let period = |polar: SunState| {
+        let is_polar = |date: NaiveDate| sun_state(date, latitude, 0.0, 0.0) == Some(polar);
+        let year_start = NaiveDate::from_yo_opt(year, 1)?;
+        let days = || {
+            year_start
+                .iter_days()
+                .take_while(|date| date.year() == year)
+        };
+
+        let Some(first) =
+            days().find(|&date| is_polar(date) && !date.pred_opt().is_some_and(is_polar))
+        else {
+            //either there's no such period, or it lasts the whole year
+            return days()
+                .all(is_polar)
+                .then(|| (year_start, days().last().unwrap_or(year_start)));
+        };
+        let last = first
+            .iter_days()
+            .take_while(|&date| is_polar(date))
+            .last()?;
+        Some((first, last))
+    };
+
+    PolarPeriods {
+        midnight_sun: period(SunState::AlwaysUp),
+        polar_night: period(SunState::AlwaysDown),
+    }
+}
+
+/// The range of directions in which the sun rises and sets, and of its altitude at solar noon, over a year, see
+/// [annual_sun_window]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunWindow {
+    /// The northernmost and southernmost azimuths at which the sun rises. Expressed as degrees clockwise from north
+    pub sunrise_azimuth_range: (f64, f64),
+    /// The southernmost and northernmost azimuths at which the sun sets. Expressed as degrees clockwise from north
+    pub sunset_azimuth_range: (f64, f64),
+    /// The highest altitude of the sun at solar noon, which is on the summer solstice, or 90° within the tropics,
+    /// where the sun passes directly overhead. Expressed as degrees
+    pub max_noon_altitude: f64,
+    /// The altitude of the sun at solar noon on the winter solstice. Expressed as degrees, being negative when the
+    /// sun doesn't rise that day
+    pub min_noon_altitude: f64,
+}
+
+/// Calculates the approximate range of directions in which the sun rises and sets over a year, and of its altitude at
+/// solar noon, at a given latitude
+///
+/// This is from the sun's declination at the solstices, when it's furthest north and south, e.g. to find how much of
+/// the sky a building's windows need to face. Where the sun doesn't rise or set on a solstice (outside ~±65° of
+/// latitude) the range of azimuths extends to due north or south, which the sun rises and sets closer to as polar
+/// day/night approaches
+///
+/// # Arguments
+///
+/// * `latitude` - The latitude at which to calculate the window. Expressed as degrees
+///
+/// # Examples
+///
+/// ```
+/// use sun_times::annual_sun_window;
+///
+/// //Calculate the window at Sheffield university's new computer science building
+/// let window = annual_sun_window(53.38);
+/// let (northernmost, southernmost) = window.sunrise_azimuth_range;
+/// println!("The sun rises between {:.0}° and {:.0}°", northernmost, southernmost);
+/// println!("It's {:.0}° up at noon in midsummer", window.max_noon_altitude);
+/// ```
+pub fn annual_sun_window(latitude: f64) -> SunWindow {
+    let solstice_declination = generic::obliquity_of_the_ecliptic(0.0);
+    //the azimuth at which the sun rises, with the given declination
+    let sunrise_azimuth = |declination: f64| {
+        let horizon_angle = SUNRISE_SUNSET_ANGLE.to_radians();
+        let (latitude, declination) = (latitude.to_radians(), declination.to_radians());
+        ((declination.sin() - horizon_angle.sin() * latitude.sin())
+            / (horizon_angle.cos() * latitude.cos()))
+        .clamp(-1.0, 1.0)
+        .acos()
+        .to_degrees()
+    };
+    let northernmost = sunrise_azimuth(solstice_declination);
+    let southernmost = sunrise_azimuth(-solstice_declination);
+    //the summer solstice is in june in the northern hemisphere, and december in the southern
+    let (summer_declination, winter_declination) = if latitude >= 0.0 {
+        (solstice_declination, -solstice_declination)
+    } else {
+        (-solstice_declination, solstice_declination)
+    };
+
+    SunWindow {
+        sunrise_azimuth_range: (northernmost, southernmost),
+        sunset_azimuth_range: (360.0 - southernmost, 360.0 - northernmost),
+        //within the tropics the sun is overhead at noon on the days its declination passes the latitude
+        max_noon_altitude: if latitude.abs() <= solstice_declination {
+            90.0
+        } else {
+            90.0 - (latitude - summer_declination).abs()
+        },
+        min_noon_altitude: 90.0 - (latitude - winter_declination).abs(),
+    }
+}
+
+/// Why the sun times couldn't be calculated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SunError {
+    /// The sun stays above the horizon for the whole day (polar day)
+    AlwaysUp,
+    /// The sun stays below the horizon for the whole day (polar night)
+    AlwaysDown,
+    /// The latitude was outside of [-90, 90] degrees, or NaN
+    InvalidLatitude,
+    /// The longitude was outside of [-180, 180] degrees, or NaN
+    InvalidLongitude,
+    /// One of the other inputs was NaN, or otherwise led to a NaN result
+    InvalidInput,
+    /// The result is not representable in chrono (~5M years from now)
+    TimestampOutOfRange,
+}
+
+impl core::fmt::Display for SunError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            SunError::AlwaysUp => "the sun does not set on this day",
+            SunError::AlwaysDown => "the sun does not rise on this day",
+            SunError::InvalidLatitude => "latitude must be between -90 and 90 degrees",
+            SunError::InvalidLongitude => "longitude must be between -180 and 180 degrees",
+            SunError::InvalidInput => "the inputs did not produce a valid result",
+            SunError::TimestampOutOfRange => "the result is not representable as a timestamp",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SunError {}
+
+/// Checks that a latitude and longitude are within [-90, 90] and [-180, 180] degrees respectively, and that the
+/// elevation isn't NaN
+fn validate_location(latitude: f64, longitude: f64, elevation: f64) -> Result<(), SunError> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        Err(SunError::InvalidLatitude)
+    } else if !(-180.0..=180.0).contains(&longitude) {
+        Err(SunError::InvalidLongitude)
+    } else if elevation.is_nan() {
+        Err(SunError::InvalidInput)
+    } else {
+        Ok(())
+    }
+}
+
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude, with the reason for
+/// any failure
+///
+/// This is the same calculation as [sun_times], but returns a [SunError] describing why the times couldn't be
+/// calculated rather than `None`
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `Err(SunError::AlwaysUp)` or `Err(SunError::AlwaysDown)` during polar day or polar night respectively
+///  - `Err(SunError::InvalidLatitude)` or `Err(SunError::InvalidLongitude)` if the latitude or longitude is outside of
+///    [-90, 90] or [-180, 180] respectively
+///  - `Err(SunError::InvalidInput)` if the elevation is NaN
+///  - `Err(SunError::TimestampOutOfRange)` if the date is not representable in chrono (~5M years from now)
+///  - `Ok((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{sun_times_checked, SunError};
+///
+/// //Check why there's no sunrise on midwinter's day in Tromsø
+/// let date = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+/// let times = sun_times_checked(date, 69.65, 18.96, 0.0);
+/// assert_eq!(times, Err(SunError::AlwaysDown));
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_checked(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), SunError> {
+    validate_location(latitude, longitude, elevation)?;
+    match generic::crossing_within_day(
+        JulianDate::from_date(date),
+        latitude,
+        longitude,
+        elevation,
+        SUNRISE_SUNSET_ANGLE,
+    )
+    .ok_or(SunError::InvalidInput)?
+    {
+        generic::Crossing::At(rise, set) => Ok((
+            rise.to_datetime().ok_or(SunError::TimestampOutOfRange)?,
+            set.to_datetime().ok_or(SunError::TimestampOutOfRange)?,
+        )),
+        generic::Crossing::AlwaysAbove => Err(SunError::AlwaysUp),
+        generic::Crossing::AlwaysBelow => Err(SunError::AlwaysDown),
+    }
+}
+
+/// Calculates the approximate time of the next sunrise strictly after a given moment, at a given latitude, longitude, and altitude
+///
+/// Elevation is handled in the same way as in [sun_times]. Days without a sunrise (during polar day/night) are skipped
+///
+/// # Arguments
+///
+/// * `after` - The moment after which to find the next sunrise
+/// * `latitude` - The latitude at which to calculate the time. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the time. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the time. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if there is no sunrise within a year of `after`, or the date is not representable in chrono
+///  - `Some(sunrise)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::next_sunrise;
+///
+/// //Find the first sunrise after midday at Sheffield university's new computer science building
+/// let after = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let sunrise = next_sunrise(after, 53.38, -1.48, 100.0).unwrap();
+/// println!("Next sunrise: {}", sunrise);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn next_sunrise(
+    after: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<DateTime<Utc>> {
+    next_event(after, latitude, longitude, elevation, |(rise, _)| rise)
+}
+
+/// Calculates the approximate time of the next sunset strictly after a given moment, at a given latitude, longitude, and altitude
+///
+/// Elevation is handled in the same way as in [sun_times]. Days without a sunset (during polar day/night) are skipped
+///
+/// # Arguments
+///
+/// * `after` - The moment after which to find the next sunset
+/// * `latitude` - The latitude at which to calculate the time. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the time. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the time. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if there is no sunset within a year of `after`, or the date is not representable in chrono
+///  - `Some(sunset)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::next_sunset;
+///
+/// //Find the first sunset after midday at Sheffield university's new computer science building
+/// let after = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let sunset = next_sunset(after, 53.38, -1.48, 100.0).unwrap();
+/// println!("Next sunset: {}", sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn next_sunset(
+    after: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<DateTime<Utc>> {
+    next_event(after, latitude, longitude, elevation, |(_, set)| set)
+}
+
+/// Calculates the approximate times of the next `n` sunrises strictly after a given moment, at a given latitude,
+/// longitude, and altitude, e.g. to add to a calendar
+///
+/// Elevation is handled in the same way as in [sun_times]. Days without a sunrise (during polar day/night) are skipped
+///
+/// # Arguments
+///
+/// * `from` - The moment after which to find the sunrises
+/// * `n` - The number of sunrises to find
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns the sunrises in order. There are fewer than `n` if a year passes without a sunrise (as at the poles), or
+/// the dates stop being representable in chrono
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::upcoming_sunrises;
+///
+/// //Find the sunrises over the next week at Sheffield university's new computer science building
+/// let from = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// for sunrise in upcoming_sunrises(from, 7, 53.38, -1.48, 100.0) {
+///     println!("Sunrise: {}", sunrise);
+/// }
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn upcoming_sunrises(
+    from: DateTime<Utc>,
+    n: usize,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Vec<DateTime<Utc>> {
+    upcoming_events(from, n, latitude, longitude, elevation, |(rise, _)| rise)
+}
+
+/// Calculates the approximate times of the next `n` sunsets strictly after a given moment, at a given latitude,
+/// longitude, and altitude, e.g. to add to a calendar
+///
+/// Elevation is handled in the same way as in [sun_times]. Days without a sunset (during polar day/night) are skipped
+///
+/// # Arguments
+///
+/// * `from` - The moment after which to find the sunsets
+/// * `n` - The number of sunsets to find
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns the sunsets in order. There are fewer than `n` if a year passes without a sunset (as at the poles), or
+/// the dates stop being representable in chrono
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::upcoming_sunsets;
+///
+/// //Find the sunsets over the next week at Sheffield university's new computer science building
+/// let from = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// for sunset in upcoming_sunsets(from, 7, 53.38, -1.48, 100.0) {
+///     println!("Sunset: {}", sunset);
+/// }
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn upcoming_sunsets(
+    from: DateTime<Utc>,
+    n: usize,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Vec<DateTime<Utc>> {
+    upcoming_events(from, n, latitude, longitude, elevation, |(_, set)| set)
+}
+
+/// Calculates how long it is from a given moment until the next sunset, at a given latitude, longitude, and altitude
+///
+/// This is the time until [next_sunset], so during polar day it's the (long) time until the sun next sets
+///
+/// # Arguments
+///
+/// * `now` - The moment from which to count
+/// * `latitude` - The latitude at which to calculate the time. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the time. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the time. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if there is no sunset within a year of `now`, or the date is not representable in chrono
+///  - `Some(duration)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::time_until_sunset;
+///
+/// //Count down to sunset from midday at Sheffield university's new computer science building
+/// let now = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let remaining = time_until_sunset(now, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunset in {} minutes", remaining.num_minutes());
+/// ```
+#[cfg(feature = "chrono")]
+pub fn time_until_sunset(
+    now: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<Duration> {
+    Some(next_sunset(now, latitude, longitude, elevation)? - now)
+}
+
+/// Calculates how far through the daylight (from sunrise to sunset) a given moment is, at a given latitude, longitude, and altitude
+///
+/// This is the time since sunrise as a fraction of the time from sunrise to sunset, e.g. for showing the sun's
+/// progress across the sky on a dashboard. The sunrise and sunset are those of [sun_times], around the given moment
+/// rather than on its UTC date
+///
+/// # Arguments
+///
+/// * `date_time` - The moment for which to calculate the progress
+/// * `latitude` - The latitude at which to calculate the progress. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the progress. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the progress. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the sun is down, or there's no sunrise or sunset to measure from due to long arctic/antarctic day/night
+///  - `Some(progress)` otherwise, from 0.0 at sunrise to 1.0 at sunset
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::solar_progress;
+///
+/// //Calculate how far the sun is across the sky at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 15, 0, 0).unwrap();
+/// let progress = solar_progress(date_time, 53.38, -1.48, 100.0).unwrap();
+/// println!("The sun is {:.0}% across the sky", progress * 100.0);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn solar_progress(
+    date_time: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<f64> {
+    //the day before and after too, as the times can fall on the adjacent UTC day far from greenwich
+    let date = date_time.date_naive();
+    let times = [date.pred_opt()?, date, date.succ_opt()?]
+        .map(|date| sun_times(date, latitude, longitude, elevation));
+    let times = times.iter().flatten();
+
+    let sunrise = times
+        .clone()
+        .map(|&(rise, _)| rise)
+        .filter(|&rise| rise <= date_time)
+        .max()?;
+    let sunset = times
+        .clone()
+        .map(|&(_, set)| set)
+        .filter(|&set| set >= date_time)
+        .min()?;
+    //if the sun has set since it rose, it's night
+    if times
+        .map(|&(_, set)| set)
+        .any(|set| sunrise < set && set < date_time)
+    {
+        return None;
+    }
+
+    let elapsed = (date_time - sunrise).num_milliseconds() as f64;
+    let daylight = (sunset - sunrise).num_milliseconds() as f64;
+    Some((elapsed / daylight).clamp(0.0, 1.0))
+}
+
+/// Finds the first event (picked out of each day's [sun_times] by `event`) strictly after `after`
+#[cfg(feature = "chrono")]
+fn next_event(
+    after: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    event: impl Fn((DateTime<Utc>, DateTime<Utc>)) -> DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    //polar night/day lasts at most half a year, so a year of days (plus the day before, as times can fall on
+    //the adjacent UTC day near the antimeridian) is always enough to find the next event
+    const DAYS_TO_SEARCH: usize = 367;
+
+    after
+        .date_naive()
+        .pred_opt()?
+        .iter_days()
+        .take(DAYS_TO_SEARCH)
+        .filter_map(|date| sun_times(date, latitude, longitude, elevation))
+        .map(event)
+        .find(|&time| time > after)
+}
+
+/// Finds the first `n` events (picked out of each day's [sun_times] by `event`) strictly after `from`, see
+/// [next_event]
+#[cfg(all(feature = "chrono", feature = "std"))]
+fn upcoming_events(
+    from: DateTime<Utc>,
+    n: usize,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    event: impl Fn((DateTime<Utc>, DateTime<Utc>)) -> DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    //as in next_event, a year without an event means there won't be another
+    const DAYS_TO_SEARCH: usize = 367;
+
+    let mut events = Vec::with_capacity(n);
+    let Some(start) = from.date_naive().pred_opt() else {
+        return events;
+    };
+    let mut last = from;
+    let mut days_without_event = 0;
+    for date in start.iter_days() {
+        if events.len() == n || days_without_event == DAYS_TO_SEARCH {
+            break;
+        }
+        //near polar day/night a day's times can be those of the one before, so only take ones later than the last
+        match sun_times(date, latitude, longitude, elevation)
+            .map(&event)
+            .filter(|&time| time > last)
+        {
+            Some(time) => {
+                events.push(time);
+                last = time;
+                days_without_event = 0;
+            }
+            None => days_without_event += 1,
+        }
+    }
+    events
+}
+
+/// Calculates the approximate length of the day (the time between sunrise and sunset) at a given latitude, longitude, and altitude
+///
+/// Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the day length, in UTC
+/// * `latitude` - The latitude at which to calculate the day length. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the day length. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the day length. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now)
+///  - `Some(Duration::zero())` during polar night
+///  - `Some(Duration::days(1))` during polar day
+///  - `Some(set - rise)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::day_length;
+///
+/// //Calculate the length of the day at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let length = day_length(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("Day length: {} minutes", length.num_minutes());
+/// ```
+#[cfg(feature = "chrono")]
+pub fn day_length(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<Duration> {
+    match sun_state(date, latitude, longitude, elevation)? {
+        SunState::Rises { rise, set } => Some(set - rise),
+        SunState::AlwaysUp => Some(Duration::days(1)),
+        SunState::AlwaysDown => Some(Duration::zero()),
+    }
+}
+
+/// Calculates the approximate sunset and sunrise times on a given date at many locations
+///
+/// This gives the same times as calling [sun_times] for each location, to within a second or so (a few seconds close to
+/// polar day/night), but calculates the sun's position along its orbit once for the date, rather than once for each
+/// location, e.g. for the cells of a weather model's grid
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `points` - The latitude, longitude, and elevation of each location, as for [sun_times]
+///
+/// # Return value
+///
+/// Returns the sunrise and sunset at each location, in the same order as `points`, each being `None` in the same
+/// cases as for [sun_times]
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_grid;
+///
+/// //Calculate the sunset and sunrise times every degree across the UK
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let points: Vec<_> = (50..=58)
+///     .flat_map(|latitude| (-6..=1).map(move |longitude| (latitude as f64, longitude as f64, 0.0)))
+///     .collect();
+/// for ((latitude, longitude, _), times) in points.iter().zip(sun_times_grid(date, &points)) {
+///     println!("{}, {}: {:?}", latitude, longitude, times);
+/// }
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn sun_times_grid(
+    date: NaiveDate,
+    points: &[(f64, f64, f64)],
+) -> Vec<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+    let start_of_day = JulianDate::from_date(date);
+    //the sunrise equation for the date and its neighbours at greenwich, plus one more either side to interpolate towards
+    let greenwich = [-2.0, -1.0, 0.0, 1.0, 2.0]
+        .map(|offset| generic::SunriseEquation::new(start_of_day + JulianDays(offset), 0.0));
+
+    points
+        .iter()
+        .map(|&(latitude, longitude, elevation)| {
+            let horizon = generic::Horizon::new(latitude, elevation, SUNRISE_SUNSET_ANGLE);
+            let crossing = generic::pick_within_day(start_of_day, |day| {
+                let index = (Float::round((day - start_of_day).0) + 2.0) as usize;
+                let days = [
+                    greenwich.get(index.checked_sub(1)?)?,
+                    greenwich.get(index)?,
+                    greenwich.get(index + 1)?,
+                ];
+                generic::SunriseEquation::interpolate_longitude(days, longitude)
+                    .crossing_horizon(&horizon)
+            })?;
+            match crossing {
+                generic::Crossing::At(rise, set) => Some((rise.to_datetime()?, set.to_datetime()?)),
+                generic::Crossing::AlwaysAbove | generic::Crossing::AlwaysBelow => None,
+            }
+        })
+        .collect()
+}
+
+/// Calculates the approximate sunset and sunrise times on a given date at many nearby locations, once for each group of them
+///
+/// The locations are grouped into cells of `tolerance` degrees of latitude and longitude, and the times are calculated
+/// with [sun_times_grid] once for the centre of each cell, at the average elevation of its locations. Every location
+/// in a cell then gets the same times, e.g. for shading the pixels of a map without a calculation for each one. Near
+/// the equator the times change by ~4 minutes for each degree of longitude, so a tolerance of 0.01° keeps them within
+/// a few seconds away from the polar circles. A tolerance which isn't positive calculates the times for every location, as [sun_times_grid]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `points` - The latitude, longitude, and elevation of each location, as for [sun_times]
+/// * `tolerance` - The size of the cells into which the locations are grouped. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns the sunrise and sunset at each location, in the same order as `points`, each being `None` in the same
+/// cases as for [sun_times] at the centre of its cell
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_grouped;
+///
+/// //Calculate the sunset and sunrise times for each pixel of a map of Sheffield, a few hundred meters apart
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let points: Vec<_> = (0..100)
+///     .flat_map(|y| (0..100).map(move |x| (53.33 + y as f64 * 0.001, -1.53 + x as f64 * 0.001, 100.0)))
+///     .collect();
+/// let times = sun_times_grouped(date, &points, 0.01);
+/// assert_eq!(times.len(), points.len());
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn sun_times_grouped(
+    date: NaiveDate,
+    points: &[(f64, f64, f64)],
+    tolerance: f64,
+) -> Vec<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+    if tolerance <= 0.0 || tolerance.is_nan() {
+        return sun_times_grid(date, points);
+    }
+
+    //the cell of each location, and the total elevation and number of locations in each cell
+    let mut cells = std::collections::HashMap::new();
+    let cell_of_points: Vec<_> = points
+        .iter()
+        .map(|&(latitude, longitude, elevation)| {
+            let key = (
+                (latitude / tolerance).round() as i64,
+                (longitude / tolerance).round() as i64,
+            );
+            let next_index = cells.len();
+            let (index, total_elevation, count) = cells.entry(key).or_insert((next_index, 0.0, 0));
+            *total_elevation += elevation;
+            *count += 1;
+            *index
+        })
+        .collect();
+
+    let mut representatives = vec![(0.0, 0.0, 0.0); cells.len()];
+    for ((latitude, longitude), (index, total_elevation, count)) in cells {
+        representatives[index] = (
+            (latitude as f64 * tolerance).clamp(-90.0, 90.0),
+            longitude as f64 * tolerance,
+            total_elevation / count as f64,
+        );
+    }
+    let times = sun_times_grid(date, &representatives);
+    cell_of_points
+        .into_iter()
+        .map(|index| times[index])
+        .collect()
+}
+
+/// Calculates the approximate time of solar noon (when the sun crosses the meridian, and is at its highest) at a given longitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate solar noon, in UTC
+/// * `longitude` - The longitude at which to calculate solar noon. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now)
+///  - `Some(solar_noon)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::solar_noon;
+///
+/// //Calculate solar noon at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let noon = solar_noon(date, -1.48).unwrap();
+/// println!("Solar noon: {}", noon);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn solar_noon(date: NaiveDate, longitude: f64) -> Option<DateTime<Utc>> {
+    generic::SunriseEquation::new(JulianDate::from_date(date), longitude)
+        .solar_transit()
+        .to_datetime()
+}
+
+/// Calculates the approximate time at which the sun is highest in the sky at a given latitude and longitude, by
+/// searching for where the sun's [altitude] stops rising
+///
+/// This is almost exactly [solar_noon], but as the sun's declination changes through the day, its highest point is
+/// slightly before or after it crosses the meridian. The difference is under a second in the tropics around the
+/// solstices, and grows to a minute or so inside the arctic and antarctic circles around the equinoxes, and to many
+/// minutes within a degree of the poles
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the time, in UTC
+/// * `latitude` - The latitude at which to calculate the time. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the time. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or the sun's altitude doesn't peak
+///    within six hours of solar noon, as can happen at the poles, where it changes with the declination rather than
+///    through the day
+///  - `Some(max_altitude_time)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::max_altitude_time;
+///
+/// //Calculate when the sun is highest at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let highest = max_altitude_time(date, 53.38, -1.48).unwrap();
+/// println!("Sun highest at: {}", highest);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn max_altitude_time(date: NaiveDate, latitude: f64, longitude: f64) -> Option<DateTime<Utc>> {
+    const PRECISION: f64 = 0.5 / SECONDS_PER_DAY as f64;
+    //the interval over which the slope of the altitude is measured
+    const STEP: f64 = 1.0 / SECONDS_PER_DAY as f64;
+
+    let transit =
+        generic::SunriseEquation::new(JulianDate::from_date(date), longitude).solar_transit();
+    //how quickly the sun is rising, `days` after solar noon
+    let slope = |days: f64| {
+        let altitude = |days: f64| {
+            generic::position(JulianDate(transit.0 + days), latitude, longitude).altitude
+        };
+        altitude(days + STEP) - altitude(days - STEP)
+    };
+
+    let (mut before, mut after) = (-0.25, 0.25);
+    if !(slope(before) > 0.0 && slope(after) < 0.0) {
+        return None;
+    }
+    while after - before > PRECISION {
+        let middle = (before + after) / 2.0;
+        if slope(middle) > 0.0 {
+            before = middle;
+        } else {
+            after = middle;
+        }
+    }
+    JulianDate(transit.0 + (before + after) / 2.0).to_datetime()
+}
+
+/// Calculates the approximate time of solar midnight (when the sun crosses the meridian below the horizon, and is at its lowest) at a given longitude
+///
+/// This is the solar midnight after the [solar_noon] of the same date, i.e. at the end of the night following that
+/// day's solar noon, even when that falls on the next UTC date. Note that [SolarDay::solar_midnight] is the one before
+/// solar noon instead
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate solar midnight, in UTC
+/// * `longitude` - The longitude at which to calculate solar midnight. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now)
+///  - `Some(solar_midnight)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::solar_midnight;
+///
+/// //Calculate solar midnight at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let midnight = solar_midnight(date, -1.48).unwrap();
+/// println!("Solar midnight: {}", midnight);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn solar_midnight(date: NaiveDate, longitude: f64) -> Option<DateTime<Utc>> {
+    let solar_noon =
+        generic::SunriseEquation::new(JulianDate::from_date(date), longitude).solar_transit();
+    JulianDate(solar_noon.0 + 0.5).to_datetime()
+}
+
+/// The main events of the sun's day, centred on solar noon
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarDay {
+    pub sunrise: DateTime<Utc>,
+    pub solar_noon: DateTime<Utc>,
+    pub sunset: DateTime<Utc>,
+    /// The solar midnight (when the sun is at its lowest) before solar noon
+    pub solar_midnight: DateTime<Utc>,
+}
+
+/// Calculates the approximate times of sunrise, solar noon, sunset, and solar midnight at a given latitude, longitude, and altitude
+///
+/// This calculates all of the times at once, which is cheaper than calling [sun_state], [solar_noon], etc.
+/// separately. As with [sun_state] the sunrise and sunset are those either side of solar noon on the given date, so far
+/// from Greenwich (beyond ~±60° of longitude) one of them may fall on the previous or next UTC date
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the times, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some(solar_day)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::solar_day;
+///
+/// //Calculate the day's events at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let day = solar_day(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Solar noon: {}, Sunset: {}", day.sunrise, day.solar_noon, day.sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn solar_day(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<SolarDay> {
+    let equation = generic::SunriseEquation::new(JulianDate::from_date(date), longitude);
+    let (rise, set) = match equation.crossing(latitude, elevation, SUNRISE_SUNSET_ANGLE)? {
+        generic::Crossing::At(rise, set) => (rise, set),
+        generic::Crossing::AlwaysAbove | generic::Crossing::AlwaysBelow => return None,
+    };
+    let solar_noon = equation.solar_transit();
+    Some(SolarDay {
+        sunrise: rise.to_datetime()?,
+        solar_noon: solar_noon.to_datetime()?,
+        sunset: set.to_datetime()?,
+        solar_midnight: JulianDate(solar_noon.0 - 0.5).to_datetime()?,
+    })
+}
+
+/// The times of the sun's events through a day, see [solar_events]
+///
+/// Each is `None` if the sun doesn't reach the altitude which marks it on that day, e.g. there is no astronomical dusk
+/// in the summer at high latitudes
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarEvents {
+    pub astronomical_dawn: Option<DateTime<Utc>>,
+    pub nautical_dawn: Option<DateTime<Utc>>,
+    pub civil_dawn: Option<DateTime<Utc>>,
+    pub sunrise: Option<DateTime<Utc>>,
+    pub morning_golden_hour: Option<Interval>,
+    pub solar_noon: Option<DateTime<Utc>>,
+    pub evening_golden_hour: Option<Interval>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub civil_dusk: Option<DateTime<Utc>>,
+    pub nautical_dusk: Option<DateTime<Utc>>,
+    pub astronomical_dusk: Option<DateTime<Utc>>,
+}
+
+/// Calculates the approximate times of all of the sun's events through a day at a given latitude, longitude, and altitude
+///
+/// This gives the same times as [sun_times], [twilight_times], [golden_hour], and [solar_noon], but calculates the
+/// sun's position for the day (and its neighbours) once for all of them, so is cheaper than calling each
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the times, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::solar_events;
+///
+/// //Calculate the day's events at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let events = solar_events(date, 53.38, -1.48, 100.0);
+/// println!("Civil dawn: {:?}, Sunrise: {:?}", events.civil_dawn, events.sunrise);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn solar_events(date: NaiveDate, latitude: f64, longitude: f64, elevation: f64) -> SolarEvents {
+    let start_of_day = JulianDate::from_date(date);
+    //the rise and set within the UTC day may come from the solar day before or after, see generic::pick_within_day
+    let equations = [-1.0, 0.0, 1.0]
+        .map(|offset| generic::SunriseEquation::new(start_of_day + JulianDays(offset), longitude));
+    let crossings = |angle| {
+        let horizon = generic::Horizon::new(latitude, elevation, angle);
+        let crossing = generic::pick_within_day(start_of_day, |day| {
+            let index = Float::round((day - start_of_day).0) + 1.0;
+            equations.get(index as usize)?.crossing_horizon(&horizon)
+        })?;
+        match crossing {
+            generic::Crossing::At(rise, set) => Some((rise.to_datetime()?, set.to_datetime()?)),
+            generic::Crossing::AlwaysAbove | generic::Crossing::AlwaysBelow => None,
+        }
+    };
+
+    let [astronomical, nautical, civil, sun, golden_lower, golden_upper] = [
+        Twilight::Astronomical.angle(),
+        Twilight::Nautical.angle(),
+        Twilight::Civil.angle(),
+        SUNRISE_SUNSET_ANGLE,
+        -4.0,
+        6.0,
+    ]
+    .map(crossings);
+    //as in golden_hour, both ends of a golden hour are needed for either
+    let (morning_golden_hour, evening_golden_hour) = match (golden_lower, golden_upper) {
+        (Some((lower_rise, lower_set)), Some((upper_rise, upper_set))) => (
+            Some(Interval {
+                start: lower_rise,
+                end: upper_rise,
+            }),
+            Some(Interval {
+                start: upper_set,
+                end: lower_set,
+            }),
+        ),
+        _ => (None, None),
+    };
+
+    SolarEvents {
+        astronomical_dawn: astronomical.map(|(dawn, _)| dawn),
+        nautical_dawn: nautical.map(|(dawn, _)| dawn),
+        civil_dawn: civil.map(|(dawn, _)| dawn),
+        sunrise: sun.map(|(rise, _)| rise),
+        morning_golden_hour,
+        solar_noon: equations[1].solar_transit().to_datetime(),
+        evening_golden_hour,
+        sunset: sun.map(|(_, set)| set),
+        civil_dusk: civil.map(|(_, dusk)| dusk),
+        nautical_dusk: nautical.map(|(_, dusk)| dusk),
+        astronomical_dusk: astronomical.map(|(_, dusk)| dusk),
+    }
+}
+
+/// The sunrise and sunset times, along with the intermediate values used to calculate them
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunTimesDetailed {
+    pub sunrise: DateTime<Utc>,
+    pub sunset: DateTime<Utc>,
+    pub solar_noon: DateTime<Utc>,
+    /// The hour angle of the sun at sunrise and sunset, i.e. how far the earth turns between sunrise and solar noon,
+    /// and between solar noon and sunset. Expressed as degrees, 15° being an hour
+    pub hour_angle: f64,
+}
+
+#[cfg(feature = "chrono")]
+impl From<SunTimesDetailed> for SunTimes {
+    fn from(times: SunTimesDetailed) -> Self {
+        Self {
+            sunrise: times.sunrise,
+            sunset: times.sunset,
+        }
+    }
+}
+
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude, along with the solar noon and hour angle between them
+///
+/// As with [solar_day] the sunrise and sunset are those either side of solar noon on the given date, so that they're
+/// both exactly `hour_angle` away from it
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the times, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some(times)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_detailed;
+///
+/// //Calculate the times at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let times = sun_times_detailed(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}, Hour angle: {}°", times.sunrise, times.sunset, times.hour_angle);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_detailed(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<SunTimesDetailed> {
+    let equation = generic::SunriseEquation::new(JulianDate::from_date(date), longitude);
+    let (rise, set) = match equation.crossing(latitude, elevation, SUNRISE_SUNSET_ANGLE)? {
+        generic::Crossing::At(rise, set) => (rise, set),
+        generic::Crossing::AlwaysAbove | generic::Crossing::AlwaysBelow => return None,
+    };
+    let solar_noon = equation.solar_transit();
+    Some(SunTimesDetailed {
+        sunrise: rise.to_datetime()?,
+        sunset: set.to_datetime()?,
+        solar_noon: solar_noon.to_datetime()?,
+        hour_angle: (set - solar_noon).0 * 360.0,
+    })
+}
+
+/// The sunrise and sunset times, along with every intermediate value of the sunrise equation, for checking where a
+/// calculation goes wrong
+///
+/// See <https://en.wikipedia.org/wiki/Sunrise_equation> for what each of the values are
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunTimesDebug {
+    /// The midnight at the start of the date
+    pub julian_date: JulianDate,
+    /// The whole number of days from J2000 (noon on the 1st of January 2000) to the date, rounded up
+    pub days_since_2000: f64,
+    /// The mean solar noon at the longitude. Expressed as days since J2000
+    pub mean_solar_time: f64,
+    /// Expressed as degrees
+    pub solar_mean_anomaly: f64,
+    /// Expressed as degrees
+    pub ecliptic_longitude: f64,
+    /// Expressed as degrees
+    pub declination: f64,
+    /// The hour angle of the sun at sunrise and sunset, or `None` if it doesn't rise and set that day. Expressed as
+    /// degrees
+    pub event_hour_angle: Option<f64>,
+    pub solar_transit: JulianDate,
+    pub sunrise: Option<DateTime<Utc>>,
+    pub sunset: Option<DateTime<Utc>>,
+}
+
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude, along with every intermediate value used to calculate them
+///
+/// This is for debugging and validating the calculation, e.g. against a worked example of the sunrise equation. As
+/// with [sun_times_detailed] the sunrise and sunset are those either side of the solar transit on the given date, so
+/// far from Greenwich one of them can be on the day before or after, unlike with [sun_times]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the times, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now)
+///  - `Some(values)` otherwise, with no sunrise and sunset if they cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_debug;
+///
+/// //Inspect the calculation at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let values = sun_times_debug(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("{:#?}", values);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_debug(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<SunTimesDebug> {
+    let julian_date = JulianDate::from_date(date);
+    let equation = generic::SunriseEquation::new(julian_date, longitude);
+    let solar_transit = equation.solar_transit();
+    let times = match equation.crossing(latitude, elevation, SUNRISE_SUNSET_ANGLE) {
+        Some(generic::Crossing::At(rise, set)) => Some((rise, set)),
+        _ => None,
+    };
+    Some(SunTimesDebug {
+        julian_date,
+        //as in the sunrise equation
+        days_since_2000: (julian_date - JAN_2000).ceil_days(),
+        mean_solar_time: equation.mean_solar_time,
+        solar_mean_anomaly: equation.solar_mean_anomaly,
+        ecliptic_longitude: equation.ecliptic_longitude,
+        declination: equation.declination.to_degrees(),
+        event_hour_angle: times.map(|(_, set)| (set - solar_transit).0 * 360.0),
+        solar_transit,
+        sunrise: times.and_then(|(rise, _)| rise.to_datetime()),
+        sunset: times.and_then(|(_, set)| set.to_datetime()),
+    })
+}
+
+/// Calculates the approximate sunrise and sunset times in local apparent solar time, at a given latitude, longitude, and altitude
+///
+/// In apparent solar time the sun crosses the meridian at exactly 12:00, so the sunrise and sunset are symmetric about
+/// noon. This is the time a sundial would show
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some((sunrise,sunset))` otherwise, both expressed as hours after solar midnight
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_solar_time;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_solar_time(date, 53.38, -1.48, 100.0).unwrap();
+/// assert!((sunrise + sunset - 24.0).abs() < 1e-9);
+/// println!("Sunrise: {:.2}h, Sunset: {:.2}h", sunrise, sunset);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_times_solar_time(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(f64, f64)> {
+    match generic::crossing(
+        JulianDate::from_date(date),
+        latitude,
+        longitude,
+        elevation,
+        SUNRISE_SUNSET_ANGLE,
+    )? {
+        generic::Crossing::At(rise, set) => {
+            //the hour angle, converted to hours, either side of noon
+            let half_day = (set - rise).0 * 12.0;
+            Some((12.0 - half_day, 12.0 + half_day))
+        }
+        generic::Crossing::AlwaysAbove | generic::Crossing::AlwaysBelow => None,
+    }
+}
+
+/// Calculates the approximate equation of time (the difference between apparent and mean solar time) on a given date
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the equation of time, in UTC
+///
+/// # Return value
+///
+/// Returns the equation of time in minutes, positive when the sun is ahead of mean solar time (i.e. a sundial reads
+/// later than a clock keeping local mean time)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::equation_of_time;
+///
+/// //Sundials run about 16 minutes fast at the start of November
+/// let date = NaiveDate::from_ymd_opt(2022, 11, 3).unwrap();
+/// let minutes = equation_of_time(date);
+/// assert!((minutes - 16.4).abs() < 0.5);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn equation_of_time(date: NaiveDate) -> f64 {
+    const MINUTES_PER_DAY: f64 = 24.0 * 60.0;
+
+    //solar transit happens early by however much the sun is ahead of mean solar time
+    -generic::SunriseEquation::new(JulianDate::from_date(date), 0.0).transit_correction()
+        * MINUTES_PER_DAY
+}
+
+/// Calculates the approximate declination of the sun (the latitude at which it is directly overhead at solar noon) on a given date
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the declination, in UTC
+///
+/// # Return value
+///
+/// Returns the declination in degrees, positive when the sun is north of the equator
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::declination;
+///
+/// //At the june solstice the sun is overhead at the tropic of cancer
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+/// assert!((declination(date) - 23.44).abs() < 0.1);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn declination(date: NaiveDate) -> f64 {
+    generic::SunriseEquation::new(JulianDate::from_date(date), 0.0)
+        .declination
+        .to_degrees()
+}
+
+/// Calculates the approximate right ascension of the sun (the angle along the celestial equator from the March equinox) on a given date
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the right ascension, in UTC
+///
+/// # Return value
+///
+/// Returns the right ascension in degrees, in the range [0, 360). Divide by 15 to get it in hours
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::right_ascension;
+///
+/// //At the june solstice the sun is a quarter of the way around from the march equinox
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+/// assert!((right_ascension(date) / 15.0 - 6.0).abs() < 0.1);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn right_ascension(date: NaiveDate) -> f64 {
+    generic::SunriseEquation::new(JulianDate::from_date(date), 0.0).right_ascension()
+}
+
+/// Calculates the sun's mean anomaly (the angle it would have moved from perihelion if the earth's orbit were circular) on a given date, as used in the sunrise equation
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the mean anomaly, in UTC
+///
+/// # Return value
+///
+/// Returns the mean anomaly in degrees, in the range [0, 360)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::solar_mean_anomaly;
+///
+/// //the earth is at perihelion in early january
+/// let date = NaiveDate::from_ymd_opt(2022, 1, 4).unwrap();
+/// assert!(solar_mean_anomaly(date) < 1.0 || solar_mean_anomaly(date) > 359.0);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn solar_mean_anomaly(date: NaiveDate) -> f64 {
+    generic::SunriseEquation::new(JulianDate::from_date(date), 0.0).solar_mean_anomaly
+}
+
+/// Calculates the sun's ecliptic longitude (the angle along the ecliptic from the March equinox) on a given date, as used in the sunrise equation
+///
+/// This is the mean anomaly, plus the equation of the centre (for the eccentricity of the earth's orbit), plus the
+/// argument of perihelion (which precesses ~1.72° a century) and 180°. This century it's within ~0.01° of the more
+/// precise longitude used for [next_equinox] and [next_solstice]
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the ecliptic longitude, in UTC
+///
+/// # Return value
+///
+/// Returns the ecliptic longitude in degrees, in the range [0, 360)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::ecliptic_longitude;
+///
+/// //At the june solstice the sun is a quarter of the way around from the march equinox
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+/// assert!((ecliptic_longitude(date) - 90.0).abs() < 1.0);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn ecliptic_longitude(date: NaiveDate) -> f64 {
+    generic::SunriseEquation::new(JulianDate::from_date(date), 0.0).ecliptic_longitude
+}
+
+/// Calculates the apparent angular diameter of the sun's disc on a given date
+///
+/// This varies by ~3% over the year, with the distance to the sun, from ~0.524° in early july (aphelion) to ~0.542°
+/// in early january (perihelion)
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the angular diameter, in UTC
+///
+/// # Return value
+///
+/// Returns the angular diameter in degrees
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::angular_diameter;
+///
+/// let date = NaiveDate::from_ymd_opt(2022, 1, 4).unwrap();
+/// println!("The sun is {:.3}° across", angular_diameter(date));
+/// ```
+#[cfg(feature = "chrono")]
+pub fn angular_diameter(date: NaiveDate) -> f64 {
+    //the sun's semi-diameter at 1 astronomical unit, see https://en.wikipedia.org/wiki/Solar_radius
+    const SEMI_DIAMETER_AT_1_AU: f64 = 959.63 / 3600.0;
+    let distance = generic::SunriseEquation::new(JulianDate::from_date(date), 0.0).distance();
+    2.0 * SEMI_DIAMETER_AT_1_AU / distance
+}
+
+/// A hemisphere of the earth, for which the seasons are opposite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hemisphere {
+    Northern,
+    Southern,
+}
+
+/// An astronomical season, running from an equinox or solstice to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+/// Calculates the sun's apparent ecliptic longitude (in degrees, in the range [0, 360)) at a given moment
+///
+/// This is the sunrise equation's ecliptic longitude, corrected for the aberration of light and the nutation of the
+/// earth's axis. Together these move the sun back by up to ~0.01°, so the equinoxes and solstices up to ~15
+/// minutes later
+#[cfg(feature = "chrono")]
+pub(crate) fn apparent_ecliptic_longitude(julian_date: JulianDate) -> f64 {
+    //see Meeus, Astronomical Algorithms, chapter 25
+    let days_since_2000 = (julian_date - JAN_2000 + delta_t(julian_date)).0;
+    //the longitude of the moon's ascending node, whose 18.6 year cycle drives the nutation
+    let ascending_node = (125.04 - 0.052954 * days_since_2000).to_radians();
+    let ecliptic_longitude: f64 = generic::SunriseEquation::at(julian_date).ecliptic_longitude;
+    generic::rem_euclid(
+        ecliptic_longitude - 0.00569 - 0.00478 * ascending_node.sin(),
+        360.0,
+    )
+}
+
+/// Calculates the astronomical season on a given date
+///
+/// The date on which an equinox or solstice falls is counted as the first day of the season it starts
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the season, in UTC
+/// * `hemisphere` - The hemisphere in which to calculate the season
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{season, Hemisphere, Season};
+///
+/// //The march equinox is the first day of spring in the northern hemisphere, and of autumn in the southern
+/// let date = NaiveDate::from_ymd_opt(2022, 3, 20).unwrap();
+/// assert_eq!(season(date, Hemisphere::Northern), Season::Spring);
+/// assert_eq!(season(date, Hemisphere::Southern), Season::Autumn);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn season(date: NaiveDate, hemisphere: Hemisphere) -> Season {
+    //the season the date ends in, so that it includes the equinox or solstice
+    let end_of_day = JulianDate(JulianDate::from_date(date).0 + 1.0);
+    let ecliptic_longitude = match hemisphere {
+        Hemisphere::Northern => apparent_ecliptic_longitude(end_of_day),
+        Hemisphere::Southern => (apparent_ecliptic_longitude(end_of_day) + 180.0) % 360.0,
+    };
+    match ecliptic_longitude {
+        longitude if longitude < 90.0 => Season::Spring,
+        longitude if longitude < 180.0 => Season::Summer,
+        longitude if longitude < 270.0 => Season::Autumn,
+        _ => Season::Winter,
+    }
+}
+
+/// Finds the next moment at which the sun's ecliptic longitude is one of the given values (in degrees)
+#[cfg(feature = "chrono")]
+fn next_ecliptic_longitude(after: DateTime<Utc>, longitudes: [f64; 2]) -> Option<DateTime<Utc>> {
+    //the mean rate (in degrees per day) at which the sun moves along the ecliptic
+    const MEAN_MOTION: f64 = 360.0 / 365.2422;
+
+    let after = JulianDate::from(after);
+    let current = apparent_ecliptic_longitude(after);
+    let (target, distance) = longitudes
+        .into_iter()
+        .map(|longitude| (longitude, generic::rem_euclid(longitude - current, 360.0)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    //the sun's speed varies by a few percent over the year, so refine the estimate
+    let mut date = JulianDate(after.0 + distance / MEAN_MOTION);
+    for _ in 0..4 {
+        let error =
+            generic::rem_euclid(target - apparent_ecliptic_longitude(date) + 540.0, 360.0) - 180.0;
+        date = JulianDate(date.0 + error / MEAN_MOTION);
+    }
+    date.to_datetime()
+}
+
+/// Calculates the approximate time of the next equinox (when the sun crosses the celestial equator)
+///
+/// This is accurate to within ~15 minutes this century
+///
+/// # Arguments
+///
+/// * `after` - The time after which to find the equinox
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the equinox is not representable in chrono (~5M years from now)
+///  - `Some(equinox)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::next_equinox;
+///
+/// //Find the first day of (northern) spring in 2022
+/// let after = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+/// let equinox = next_equinox(after).unwrap();
+/// println!("Equinox: {}", equinox);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn next_equinox(after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    next_ecliptic_longitude(after, [0.0, 180.0])
+}
+
+/// Calculates the approximate time of the next solstice (when the sun is furthest from the celestial equator)
+///
+/// This is accurate to within ~15 minutes this century
+///
+/// # Arguments
+///
+/// * `after` - The time after which to find the solstice
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the solstice is not representable in chrono (~5M years from now)
+///  - `Some(solstice)` otherwise
+#[cfg(feature = "chrono")]
+pub fn next_solstice(after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    next_ecliptic_longitude(after, [90.0, 270.0])
+}
+
+/// Calculates the altitude of the sun at solar noon (its highest altitude of the day) on a given date and latitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the altitude, in UTC
+/// * `latitude` - The latitude at which to calculate the altitude. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns the altitude in degrees, which is negative if the sun doesn't rise at all (ignoring refraction)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::max_altitude;
+///
+/// //Calculate how high the sun gets at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+/// let altitude = max_altitude(date, 53.38);
+/// assert!((altitude - 60.06).abs() < 0.1);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn max_altitude(date: NaiveDate, latitude: f64) -> f64 {
+    90.0 - (latitude - declination(date)).abs()
+}
+
+/// Calculates the hour angle (how far the sun is from solar noon) at which the sun's centre is at a given altitude on a given date and latitude
+///
+/// This is the inverse of [altitude] through the day, and the building block of [sun_times_with_angle], for building
+/// other queries on. The sun is at the altitude twice a day, this hour angle before and
+/// after solar noon, where 15° is an hour. Neither refraction nor the observer's elevation are taken into account
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the hour angle, in UTC
+/// * `latitude` - The latitude at which to calculate the hour angle. Expressed as degrees
+/// * `target_altitude` - The altitude of the sun's centre above the horizon. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the sun is above or below the altitude all day
+///  - `Some(hour_angle)` otherwise, in degrees between 0 and 180
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::hour_angle_at_altitude;
+///
+/// //Calculate how long before and after solar noon the sun is 10° up at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+/// let hour_angle = hour_angle_at_altitude(date, 53.38, 10.0).unwrap();
+/// println!("Hours from solar noon: {}", hour_angle / 15.0);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn hour_angle_at_altitude(date: NaiveDate, latitude: f64, target_altitude: f64) -> Option<f64> {
+    let declination = generic::SunriseEquation::new(JulianDate::from_date(date), 0.0).declination;
+    generic::Horizon::new(latitude, 0.0, target_altitude).hour_angle(declination)
+}
+
+/// Calculates the altitude (angle from the horizon) of the sun at a given place and moment
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the sunset and sunrise
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns the altitude in degrees
+///
+/// # Notes
+///
+/// This currently does not include an altitude correction like the [sun_times] function does, but *is* usable within arctic regions
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use sun_times::altitude;
+///
+/// //Calculate the altitude of the sun now at Sheffield university's new computer science building
+/// let altitude = altitude(Utc::now(), 53.38, -1.48);
+/// println!("Altitude: {}", altitude);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn altitude(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+    sun_position(date_time, latitude, longitude).altitude
+}
+
+/// Calculates the apparent altitude (angle from the horizon) of the sun at a given place and moment, including the
+/// effect of atmospheric refraction
+///
+/// Refraction makes the sun appear higher than it geometrically is, by about half a degree at the horizon and
+/// decreasing to nothing at the zenith. So at the moment of sunrise/sunset (as calculated by [sun_times]), when the
+/// [altitude] is about -0.83°, the refraction is about 0.57° and the apparent altitude of the sun's centre is about
+/// -0.26° (i.e. the upper edge of the sun is touching the horizon)
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the apparent altitude
+/// * `latitude` - The latitude at which to calculate the apparent altitude. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the apparent altitude. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns the apparent altitude in degrees
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::apparent_altitude;
+///
+/// //Calculate the apparent altitude of the sun at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let altitude = apparent_altitude(date_time, 53.38, -1.48);
+/// println!("Apparent altitude: {}", altitude);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn apparent_altitude(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+    let altitude = altitude(date_time, latitude, longitude);
+    altitude + refraction(altitude)
+}
+
+/// Calculates whether the sun is up (between sunrise and sunset) at a given place and moment
+///
+/// This compares the sun's [altitude] against the same angle as [sun_times] uses, so it agrees with the sunrise and
+/// sunset times, but also handles polar day/night. Elevation is handled in the same way as in [sun_times]
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time at which to check whether the sun is up
+/// * `latitude` - The latitude at which to check whether the sun is up. Expressed as degrees
+/// * `longitude` - The longitude at which to check whether the sun is up. Expressed as degrees
+/// * `elevation` - The elevation at which to check whether the sun is up. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::is_daytime;
+///
+/// //Check whether it's daytime at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// assert!(is_daytime(date_time, 53.38, -1.48, 100.0));
+/// ```
+#[cfg(feature = "chrono")]
+pub fn is_daytime(date_time: DateTime<Utc>, latitude: f64, longitude: f64, elevation: f64) -> bool {
+    altitude(date_time, latitude, longitude) > sunrise_sunset_altitude(elevation)
+}
+
+/// A sunrise or sunset seen by a moving observer, see [daylight_transitions]
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DaylightTransition {
+    pub time: DateTime<Utc>,
+    /// Expressed as degrees
+    pub latitude: f64,
+    /// Expressed as degrees
+    pub longitude: f64,
+    /// `true` if the sun rises, or `false` if it sets
+    pub is_sunrise: bool,
+}
+
+/// Finds the approximate sunrises and sunsets seen by an observer moving along a track, e.g. an aircraft
+///
+/// The sun's [altitude] is calculated at each point of the track, and each time it crosses the same angle as
+/// [sun_times] uses between two points, the time and place of the crossing are interpolated between them. Points
+/// should be close enough together (e.g. a few minutes apart) that the sun can't rise and set again between them.
+/// The observer is treated as being at sea level, so the dip of the horizon seen from altitude isn't accounted for
+///
+/// # Arguments
+///
+/// * `positions` - The points of the track, as the time, latitude, and longitude (both expressed as degrees) of each,
+///   in time order
+///
+/// # Return value
+///
+/// Returns each sunrise and sunset along the track, in order
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use sun_times::daylight_transitions;
+///
+/// //Fly west from London to New York over 7 hours, in the evening
+/// let departure = Utc.with_ymd_and_hms(2022, 6, 1, 17, 0, 0).unwrap();
+/// let track = (0..=420).map(|minute| {
+///     let progress = f64::from(minute) / 420.0;
+///     let time = departure + Duration::minutes(minute.into());
+///     (time, 51.47 - 10.8 * progress, -0.45 - 73.33 * progress)
+/// });
+/// for transition in daylight_transitions(track) {
+///     println!("{} at {}, {}", transition.time, transition.latitude, transition.longitude);
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+pub fn daylight_transitions(
+    positions: impl Iterator<Item = (DateTime<Utc>, f64, f64)>,
+) -> impl Iterator<Item = DaylightTransition> {
+    let threshold = sunrise_sunset_altitude(0.0);
+    let mut previous: Option<(DateTime<Utc>, f64, f64, f64)> = None;
+    positions.filter_map(move |(time, latitude, longitude)| {
+        //how far the sun is above the horizon
+        let height = altitude(time, latitude, longitude) - threshold;
+        let transition = previous.and_then(
+            |(previous_time, previous_latitude, previous_longitude, previous_height)| {
+                if (previous_height < 0.0) == (height < 0.0) {
+                    return None;
+                }
+                let fraction = previous_height / (previous_height - height);
+                let milliseconds = (time - previous_time).num_milliseconds() as f64 * fraction;
+                //go the short way round, in case the track crosses the antimeridian
+                let longitude_change =
+                    generic::rem_euclid(longitude - previous_longitude + 540.0, 360.0) - 180.0;
+                Some(DaylightTransition {
+                    time: previous_time + Duration::milliseconds(milliseconds.round() as i64),
+                    latitude: previous_latitude + (latitude - previous_latitude) * fraction,
+                    longitude: generic::rem_euclid(
+                        previous_longitude + longitude_change * fraction + 540.0,
+                        360.0,
+                    ) - 180.0,
+                    is_sunrise: previous_height < 0.0,
+                })
+            },
+        );
+        previous = Some((time, latitude, longitude, height));
+        transition
+    })
+}
+
+/// The altitude of the sun's centre at sunrise/sunset when seen from a given elevation (in meters), in degrees
+#[cfg(feature = "chrono")]
+fn sunrise_sunset_altitude(elevation: f64) -> f64 {
+    //from higher up, the sun can be seen further below the horizontal
+    let horizon_dip = 2.076 * elevation.max(0.0).sqrt() / 60.0;
+    (SUNRISE_SUNSET_ANGLE - horizon_dip).max(-90.0)
+}
+
+/// Calculates the approximate atmospheric refraction (in degrees) of the sun at a given geometric altitude (in degrees)
+#[cfg(feature = "chrono")]
+fn refraction(altitude: f64) -> f64 {
+    //see https://en.wikipedia.org/wiki/Atmospheric_refraction#Calculating_refraction
+    //this uses Sæmundsson's formula (the inverse of Bennett's formula, as we start from the geometric altitude). It
+    //blows up further below the horizon, so the refraction there is held at its value at the apparent horizon, which
+    //is the HORIZON_REFRACTION assumed by the sunrise/sunset angle
+
+    const ARC_MINUTES_PER_DEGREE: f64 = 60.0;
+
+    let altitude = altitude.max(-HORIZON_REFRACTION);
+    1.02 / (altitude + 10.3 / (altitude + 5.11)).to_radians().tan() / ARC_MINUTES_PER_DEGREE
+}
+
+/// Calculates the azimuth (compass bearing) of the sun at a given place and moment
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the azimuth
+/// * `latitude` - The latitude at which to calculate the azimuth. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the azimuth. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns the azimuth in degrees clockwise from north, in the range [0, 360)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::azimuth;
+///
+/// //Calculate the azimuth of the sun at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let azimuth = azimuth(date_time, 53.38, -1.48);
+/// println!("Azimuth: {}", azimuth);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn azimuth(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+    sun_position(date_time, latitude, longitude).azimuth
+}
+
+/// Calculates the altitude (angle from the horizon) of the sun at a given place and moment, all expressed as radians
+///
+/// This is [altitude] for callers already working in radians, which skips converting the latitude, longitude, and altitude
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the altitude
+/// * `latitude` - The latitude at which to calculate the altitude. Expressed as radians
+/// * `longitude` - The longitude at which to calculate the altitude. Expressed as radians
+///
+/// # Return value
+///
+/// Returns the altitude in radians
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::altitude_rad;
+///
+/// //Calculate the altitude of the sun at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let altitude = altitude_rad(date_time, 0.9317, -0.0258);
+/// println!("Altitude: {}", altitude);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn altitude_rad(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+    generic::position_radians(JulianDate::from(date_time), latitude, longitude).0
+}
+
+/// Calculates the azimuth (compass bearing) of the sun at a given place and moment, all expressed as radians
+///
+/// This is [azimuth] for callers already working in radians, which skips converting the latitude, longitude, and azimuth
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the azimuth
+/// * `latitude` - The latitude at which to calculate the azimuth. Expressed as radians
+/// * `longitude` - The longitude at which to calculate the azimuth. Expressed as radians
+///
+/// # Return value
+///
+/// Returns the azimuth in radians clockwise from north, in the range [0, 2π)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::azimuth_rad;
+///
+/// //Calculate the azimuth of the sun at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let azimuth = azimuth_rad(date_time, 0.9317, -0.0258);
+/// println!("Azimuth: {}", azimuth);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn azimuth_rad(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+    generic::position_radians(JulianDate::from(date_time), latitude, longitude).1
+}
+
+/// Calculates the approximate azimuth (compass bearing) of the sun at sunrise at a given latitude, longitude, and altitude, in degrees clockwise from north
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunrise azimuth, in UTC
+/// * `latitude` - The latitude at which to calculate the sunrise azimuth. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sunrise azimuth. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the sunrise azimuth. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some(azimuth)` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sunrise_azimuth;
+///
+/// //Calculate where the sun rises at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let azimuth = sunrise_azimuth(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("The sun rises at a bearing of {:.0}°", azimuth);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sunrise_azimuth(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<f64> {
+    let (rise, _) = sun_times(date, latitude, longitude, elevation)?;
+    Some(azimuth(rise, latitude, longitude))
+}
+
+/// Calculates the approximate azimuth (compass bearing) of the sun at sunset at a given latitude, longitude, and altitude, in degrees clockwise from north
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset azimuth, in UTC
+/// * `latitude` - The latitude at which to calculate the sunset azimuth. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sunset azimuth. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the sunset azimuth. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some(azimuth)` otherwise
+#[cfg(feature = "chrono")]
+pub fn sunset_azimuth(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<f64> {
+    let (_, set) = sun_times(date, latitude, longitude, elevation)?;
+    Some(azimuth(set, latitude, longitude))
+}
+
+/// Calculates the approximate time at which the sun is at a given azimuth (compass bearing), and its altitude then, e.g. to find when it lines up with a street
+///
+/// This samples the sun's azimuth through the UTC day and refines the time by bisection. The sun may be below the
+/// horizon at the time found, so check the altitude. If the sun passes the azimuth more than once in the day, as it
+/// can in the tropics, the time at which it's highest is returned
+///
+/// # Arguments
+///
+/// * `date` - The date on which to find the time, in UTC
+/// * `latitude` - The latitude at which to find the time. Expressed as degrees
+/// * `longitude` - The longitude at which to find the time. Expressed as degrees
+/// * `target_azimuth` - The azimuth of the sun to find. Expressed as degrees clockwise from north
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or the sun doesn't pass the azimuth within the UTC day
+///  - `Some((time, altitude))` otherwise, with the altitude expressed as degrees
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::time_at_azimuth;
+///
+/// //Find when the sun sets along Manhattan's streets, which run 29° north of west (Manhattanhenge)
+/// let date = NaiveDate::from_ymd_opt(2022, 7, 12).unwrap();
+/// let (time, altitude) = time_at_azimuth(date, 40.78, -73.97, 299.0).unwrap();
+/// println!("The sun is along the streets at {}, {}° above the horizon", time, altitude);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn time_at_azimuth(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    target_azimuth: f64,
+) -> Option<(DateTime<Utc>, f64)> {
+    //short enough that the sun's azimuth doesn't swing by a quarter turn between samples, except very close to the zenith
+    const SAMPLES_PER_DAY: u32 = 144;
+    const PRECISION: f64 = 0.5 / SECONDS_PER_DAY as f64;
+
+    let start_of_day = JulianDate::from_date(date);
+    let position =
+        |days: f64| generic::position(JulianDate(start_of_day.0 + days), latitude, longitude);
+    //how far the sun's azimuth is past the target, in the range [-180, 180)
+    let offset = |days: f64| {
+        generic::rem_euclid(position(days).azimuth - target_azimuth + 540.0, 360.0) - 180.0
+    };
+
+    let mut best: Option<(f64, f64)> = None;
+    for sample in 0..SAMPLES_PER_DAY {
+        let (mut before, mut after) = (
+            f64::from(sample) / f64::from(SAMPLES_PER_DAY),
+            f64::from(sample + 1) / f64::from(SAMPLES_PER_DAY),
+        );
+        let (offset_before, offset_after) = (offset(before), offset(after));
+        //a change of sign across the opposite bearing isn't a crossing of the target
+        if (offset_before < 0.0) == (offset_after < 0.0)
+            || offset_before.abs() > 90.0
+            || offset_after.abs() > 90.0
+        {
+            continue;
+        }
+        let increasing = offset_before < 0.0;
+        while after - before > PRECISION {
+            let middle = (before + after) / 2.0;
+            if (offset(middle) < 0.0) == increasing {
+                before = middle;
+            } else {
+                after = middle;
+            }
+        }
+        let days = (before + after) / 2.0;
+        let altitude = position(days).altitude;
+        let better = match best {
+            Some((_, best_altitude)) => altitude > best_altitude,
+            None => true,
+        };
+        if better {
+            best = Some((days, altitude));
+        }
+    }
+    let (days, altitude) = best?;
+    Some((JulianDate(start_of_day.0 + days).to_datetime()?, altitude))
+}
+
+/// Calculates the solar zenith angle (angle from directly overhead) of the sun at a given place and moment, in degrees
+///
+/// This is the complement of [altitude], i.e. `90 - altitude`
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the zenith angle
+/// * `latitude` - The latitude at which to calculate the zenith angle. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the zenith angle. Expressed as degrees
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::solar_zenith_angle;
+///
+/// //Calculate the zenith angle of the sun at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let zenith_angle = solar_zenith_angle(date_time, 53.38, -1.48);
+/// println!("Zenith angle: {}", zenith_angle);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn solar_zenith_angle(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+    90.0 - altitude(date_time, latitude, longitude)
+}
+
+/// Calculates the length of the shadow cast on flat ground by a vertical object at a given place and moment
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the shadow length
+/// * `latitude` - The latitude at which to calculate the shadow length. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the shadow length. Expressed as degrees
+/// * `object_height` - The height of the object casting the shadow
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the sun is at or below the horizon, so there is no shadow
+///  - `Some(length)` otherwise, in the same units as `object_height`
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::shadow_length;
+///
+/// //Calculate the length of the shadow of a 2m tall person at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let length = shadow_length(date_time, 53.38, -1.48, 2.0).unwrap();
+/// println!("Shadow length: {}m", length);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn shadow_length(
+    date_time: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+    object_height: f64,
+) -> Option<f64> {
+    let altitude = altitude(date_time, latitude, longitude);
+    if altitude <= 0.0 {
+        return None;
+    }
+    Some(object_height / altitude.to_radians().tan())
+}
+
+/// The position of the sun in the sky, as seen by an observer
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunPosition<F = f64> {
+    /// The angle of the sun above the horizon. Expressed as degrees
+    pub altitude: F,
+    /// The compass bearing of the sun. Expressed as degrees clockwise from north, in the range [0, 360)
+    pub azimuth: F,
+}
+
+/// Calculates both the altitude and azimuth of the sun at a given place and moment
+///
+/// This is cheaper than calling both [altitude] and [azimuth], as the sun's position only has to be calculated once
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the sun's position
+/// * `latitude` - The latitude at which to calculate the sun's position. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sun's position. Expressed as degrees
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::sun_position;
+///
+/// //Calculate the position of the sun at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let position = sun_position(date_time, 53.38, -1.48);
+/// println!("Altitude: {}, Azimuth: {}", position.altitude, position.azimuth);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_position(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> SunPosition {
+    generic::sun_position(date_time, latitude, longitude)
+}
+
+/// Calculates the direction of the sun at a given place and moment, as a unit vector, e.g. for lighting a 3D scene
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the sun's direction
+/// * `latitude` - The latitude at which to calculate the sun's direction. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sun's direction. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns the unit vector `[east, north, up]` pointing from the observer towards the sun, so the up component is
+/// negative while the sun is below the horizon
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::sun_vector;
+///
+/// //Calculate the direction of the sun at Sheffield university's new computer science building
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+/// let [east, north, up] = sun_vector(date_time, 53.38, -1.48);
+/// println!("East: {}, North: {}, Up: {}", east, north, up);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_vector(date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> [f64; 3] {
+    let SunPosition { altitude, azimuth } = sun_position(date_time, latitude, longitude);
+    let (altitude, azimuth) = (altitude.to_radians(), azimuth.to_radians());
+    [
+        altitude.cos() * azimuth.sin(),
+        altitude.cos() * azimuth.cos(),
+        altitude.sin(),
+    ]
+}
+
+/// Calculates the subsolar point, where the sun is directly overhead, at a given moment
+///
+/// The latitude of the subsolar point is the sun's declination, and it moves west by 15° an hour, passing over
+/// Greenwich at solar noon there. This is e.g. the centre of the daylit half of the earth when drawing the day/night
+/// terminator
+///
+/// # Arguments
+///
+/// * `date_time` - The moment at which to calculate the subsolar point
+///
+/// # Return value
+///
+/// Returns the latitude and longitude of the subsolar point, both expressed as degrees, with the longitude in
+/// [-180, 180)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::subsolar_point;
+///
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 21, 12, 0, 0).unwrap();
+/// let (latitude, longitude) = subsolar_point(date_time);
+/// println!("The sun is overhead at {}, {}", latitude, longitude);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn subsolar_point(date_time: DateTime<Utc>) -> (f64, f64) {
+    let (declination, greenwich_hour_angle): (f64, f64) =
+        generic::declination_and_hour_angle(JulianDate::from(date_time), 0.0);
+    //the local hour angle is 0 where the sun is overhead, and grows by 1° for each degree east
+    let longitude = generic::rem_euclid(180.0 - greenwich_hour_angle, 360.0) - 180.0;
+    (declination.to_degrees(), longitude)
+}
+
+/// Calculates points along the terminator, the line between day and night, at a given moment
+///
+/// The terminator is the great circle 90° from the [subsolar_point], i.e. where the sun's centre is on the horizon.
+/// No allowance is made for the sun's apparent radius or atmospheric refraction, which put the line where sunrise and
+/// sunset are seen ~100km further into the night
+///
+/// # Arguments
+///
+/// * `date_time` - The moment at which to calculate the terminator
+/// * `num_points` - How many points to calculate, evenly spaced around the terminator
+///
+/// # Return value
+///
+/// Returns the latitude and longitude of each point, both expressed as degrees, with the longitude in [-180, 180).
+/// The points go around the terminator in order, starting at its northernmost point, and the last point is not
+/// repeated at the end
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sun_times::terminator;
+///
+/// //Calculate the terminator as a polyline of one point per degree, e.g. to draw onto a map
+/// let date_time = Utc.with_ymd_and_hms(2022, 6, 21, 12, 0, 0).unwrap();
+/// for (latitude, longitude) in terminator(date_time, 360) {
+///     println!("{}, {}", latitude, longitude);
+/// }
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn terminator(date_time: DateTime<Utc>, num_points: usize) -> Vec<(f64, f64)> {
+    let (subsolar_latitude, subsolar_longitude) = subsolar_point(date_time);
+    let subsolar_latitude = subsolar_latitude.to_radians();
+    (0..num_points)
+        .map(|point| {
+            //the point 90° from the subsolar point along the given bearing,
+            //see https://www.movable-type.co.uk/scripts/latlong.html#dest-point
+            let bearing = (360.0 * point as f64 / num_points as f64).to_radians();
+            let latitude = (subsolar_latitude.cos() * bearing.cos()).asin();
+            let longitude_offset = (bearing.sin() * subsolar_latitude.cos())
+                .atan2(-subsolar_latitude.sin() * latitude.sin());
+            let longitude = (subsolar_longitude + longitude_offset.to_degrees() + 180.0)
+                .rem_euclid(360.0)
+                - 180.0;
+            (latitude.to_degrees(), longitude)
+        })
+        .collect()
+}
+
+/// Calculates the position of the sun at local mean noon on every day of a year, which traces out the analemma
+///
+/// # Arguments
+///
+/// * `year` - The year over which to calculate the analemma
+/// * `latitude` - The latitude at which to calculate the analemma. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the analemma. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns each date of the year, with the sun's position at local mean noon (12:00 UTC, adjusted by 4 minutes per
+/// degree of longitude) on it. This is empty if the year is not representable in chrono
+///
+/// # Examples
+///
+/// ```
+/// use sun_times::analemma;
+///
+/// //Calculate the analemma at Sheffield university's new computer science building
+/// for (date, position) in analemma(2022, 53.38, -1.48) {
+///     println!("{}: Altitude: {}, Azimuth: {}", date, position.altitude, position.azimuth);
+/// }
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn analemma(year: i32, latitude: f64, longitude: f64) -> Vec<(NaiveDate, SunPosition)> {
+    let Some(start) = NaiveDate::from_yo_opt(year, 1) else {
+        return Vec::new();
+    };
+    start
+        .iter_days()
+        .take_while(|date| date.year() == year)
+        .map(|date| {
+            let mean_noon = JulianDate(JulianDate::from_date(date).0 + 0.5 - longitude / 360.0);
+            (date, generic::position(mean_noon, latitude, longitude))
+        })
+        .collect()
+}
+
+/// Calculates the altitude of the sun at evenly spaced moments through every day of a year, e.g. to draw a heatmap
+///
+/// Each day's altitudes are calculated from its [DaySolarParams], so are within about 0.001° of those from [altitude]
+///
+/// # Arguments
+///
+/// * `year` - The year over which to calculate the altitudes
+/// * `latitude` - The latitude at which to calculate the altitudes. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the altitudes. Expressed as degrees
+/// * `samples_per_day` - How many altitudes to calculate on each day, the first being at midnight UTC and the rest
+///   evenly spaced after it
+///
+/// # Return value
+///
+/// Returns a row for each date of the year, holding the altitudes of the sun on it in degrees. This is empty if the
+/// year is not representable in chrono
+///
+/// # Examples
+///
+/// ```
+/// use sun_times::altitude_grid;
+///
+/// //Calculate the altitude of the sun every hour of 2022 at Sheffield university's new computer science building
+/// let grid = altitude_grid(2022, 53.38, -1.48, 24);
+/// println!("Altitude at noon on the 1st of June: {}", grid[151][12]);
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn altitude_grid(
+    year: i32,
+    latitude: f64,
+    longitude: f64,
+    samples_per_day: usize,
+) -> Vec<Vec<f64>> {
+    let Some(start) = NaiveDate::from_yo_opt(year, 1) else {
+        return Vec::new();
+    };
+    start
+        .iter_days()
+        .take_while(|date| date.year() == year)
+        .map(|date| {
+            let params = DaySolarParams::for_date(date);
+            let midnight = date.and_time(Default::default()).and_utc();
+            (0..samples_per_day)
+                .map(|sample| {
+                    let seconds = SECONDS_PER_DAY as f64 * sample as f64 / samples_per_day as f64;
+                    let date_time =
+                        midnight + Duration::milliseconds((seconds * 1000.0).round() as i64);
+                    params.altitude(date_time, latitude, longitude)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The sun's position among the stars on a given day, precalculated so that its position in the sky can be cheaply
+/// calculated at many moments within that day
+///
+/// The sun moves slowly and steadily enough against the stars that its position is interpolated between the start and
+/// end of the day, so positions within the day are within about 0.001° of those from [sun_position]
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::DaySolarParams;
+///
+/// //Calculate the altitude of the sun every hour at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let params = DaySolarParams::for_date(date);
+/// for hour in 0..24 {
+///     let date_time = date.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+///     println!("{}: {}", hour, params.altitude(date_time, 53.38, -1.48));
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DaySolarParams {
+    /// The (UTC) date which these are for
+    pub date: NaiveDate,
+    /// The latitude at which the sun is directly overhead, at the start of the date. Expressed as degrees
+    pub declination: f64,
+    /// At the start of the date. Expressed as degrees
+    pub right_ascension: f64,
+    /// At the start of the date. Expressed as degrees
+    pub ecliptic_longitude: f64,
+    /// How far the declination moves over the date. Expressed as degrees
+    pub declination_change: f64,
+    /// How far the right ascension moves over the date. Expressed as degrees
+    pub right_ascension_change: f64,
+}
+
+#[cfg(feature = "chrono")]
+impl DaySolarParams {
+    /// Calculates the sun's position among the stars on a given (UTC) date
+    pub fn for_date(date: NaiveDate) -> Self {
+        let start = JulianDate::from_date(date);
+        let equation = generic::SunriseEquation::<f64>::at(start);
+        let next_equation = generic::SunriseEquation::<f64>::at(start + JulianDays(1.0));
+        let right_ascension = equation.right_ascension();
+        Self {
+            date,
+            declination: equation.declination.to_degrees(),
+            right_ascension,
+            ecliptic_longitude: equation.ecliptic_longitude,
+            declination_change: (next_equation.declination - equation.declination).to_degrees(),
+            //go the short way round, as the right ascension wraps around at the March equinox
+            right_ascension_change: generic::rem_euclid(
+                next_equation.right_ascension() - right_ascension + 540.0,
+                360.0,
+            ) - 180.0,
+        }
+    }
+
+    /// Calculates the altitude of the sun at a given place and moment within the day, in degrees
+    ///
+    /// See [altitude]
+    pub fn altitude(&self, date_time: DateTime<Utc>, latitude: f64, longitude: f64) -> f64 {
+        self.sun_position(date_time, latitude, longitude).altitude
+    }
+
+    /// Calculates both the altitude and azimuth of the sun at a given place and moment within the day
+    ///
+    /// See [sun_position]
+    pub fn sun_position(
+        &self,
+        date_time: DateTime<Utc>,
+        latitude: f64,
+        longitude: f64,
+    ) -> SunPosition {
+        let julian_date = JulianDate::from(date_time);
+        let days = (julian_date - JulianDate::from_date(self.date)).0;
+        let declination = self.declination + self.declination_change * days;
+        let right_ascension = self.right_ascension + self.right_ascension_change * days;
+        let local_sidereal_time = generic::greenwich_sidereal_time(julian_date) + longitude;
+        generic::horizontal_position(
+            declination.to_radians(),
+            local_sidereal_time - right_ascension,
+            latitude,
+        )
+    }
+}
+
+/// Calculates the position of the sun at a given place, at each of many moments
+///
+/// The sun's position among the stars is calculated once for each UTC date, using [DaySolarParams], so this is much
+/// cheaper than calling [sun_position] for each moment when they're grouped by date (e.g. in order), and gives
+/// positions within about 0.001° of it
+///
+/// # Arguments
+///
+/// * `times` - The dates and times at which to calculate the positions
+/// * `latitude` - The latitude at which to calculate the positions. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the positions. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns the position of the sun at each of `times`, in the same order
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use sun_times::sun_positions;
+///
+/// //Trace the sun's path every 10 minutes at Sheffield university's new computer science building
+/// let start = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+/// let times: Vec<_> = (0..144).map(|i| start + Duration::minutes(10 * i)).collect();
+/// for (time, position) in times.iter().zip(sun_positions(&times, 53.38, -1.48)) {
+///     println!("{}: {}, {}", time, position.altitude, position.azimuth);
+/// }
+/// ```
+#[cfg(all(feature = "chrono", feature = "std"))]
+pub fn sun_positions(times: &[DateTime<Utc>], latitude: f64, longitude: f64) -> Vec<SunPosition> {
+    let mut day = None;
+    times
+        .iter()
+        .map(|&date_time| day_position(&mut day, date_time, latitude, longitude))
+        .collect()
+}
+
+/// Calculates the position of the sun at steps through a period of time, at a given place, e.g. to animate its path
+///
+/// As with [sun_positions], the sun's position among the stars is calculated once for each UTC date, and the positions
+/// are within about 0.001° of those from [sun_position]. They're calculated as the iterator is advanced
+///
+/// # Arguments
+///
+/// * `start` - The first moment at which to calculate the position
+/// * `end` - The moment after which to stop
+/// * `step` - The time between positions
+/// * `latitude` - The latitude at which to calculate the positions. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the positions. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns an iterator over each moment from `start` to `end` (inclusive), `step` apart, along with the position of
+/// the sun at that moment. This is empty if `step` isn't positive
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use sun_times::sun_track;
+///
+/// //Trace the sun's path every 10 minutes at Sheffield university's new computer science building
+/// let start = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2022, 6, 2, 0, 0, 0).unwrap();
+/// for (time, position) in sun_track(start, end, Duration::minutes(10), 53.38, -1.48) {
+///     println!("{}: {}, {}", time, position.altitude, position.azimuth);
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sun_track(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    step: Duration,
+    latitude: f64,
+    longitude: f64,
+) -> impl Iterator<Item = (DateTime<Utc>, SunPosition)> {
+    let mut day = None;
+    core::iter::successors(
+        Some(start).filter(|_| step > Duration::zero()),
+        move |&date_time| date_time.checked_add_signed(step),
+    )
+    .take_while(move |&date_time| date_time <= end)
+    .map(move |date_time| {
+        (
+            date_time,
+            day_position(&mut day, date_time, latitude, longitude),
+        )
+    })
+}
+
+/// Calculates the position of the sun at a given place and moment, reusing the [DaySolarParams] in `day` if they're
+/// for the same UTC date, and replacing them otherwise
+#[cfg(feature = "chrono")]
+fn day_position(
+    day: &mut Option<(NaiveDate, DaySolarParams)>,
+    date_time: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+) -> SunPosition {
+    let date = date_time.date_naive();
+    let params = match *day {
+        Some((day_date, params)) if day_date == date => params,
+        _ => {
+            let params = DaySolarParams::for_date(date);
+            *day = Some((date, params));
+            params
+        }
+    };
+    params.sun_position(date_time, latitude, longitude)
+}
+
+/// A location on the earth from which the sun is observed
+///
+/// This saves repeating the latitude, longitude, and elevation for every calculation at a fixed location, and avoids
+/// accidentally swapping them. Each method is equivalent to the free function of the same name
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::Observer;
+///
+/// //Sheffield university's new computer science building
+/// let observer = Observer {
+///     latitude: 53.38,
+///     longitude: -1.48,
+///     elevation: 100.0,
+/// };
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = observer.sun_times(date).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observer {
+    /// Expressed as degrees
+    pub latitude: f64,
+    /// Expressed as degrees
+    pub longitude: f64,
+    /// Expressed as meters above sea level
+    pub elevation: f64,
+}
+
+impl Observer {
+    /// Creates an observer at a given latitude, longitude, and elevation, checking that they're valid
+    ///
+    /// # Arguments
+    ///
+    /// * `latitude` - The latitude of the observer. Expressed as degrees
+    /// * `longitude` - The longitude of the observer. Expressed as degrees
+    /// * `elevation` - The elevation of the observer. Expressed as meters above sea level
+    ///
+    /// # Return value
+    ///
+    /// Returns
+    ///  - `Err(SunError::InvalidLatitude)` or `Err(SunError::InvalidLongitude)` if the latitude or longitude is outside
+    ///    of [-90, 90] or [-180, 180] respectively
+    ///  - `Err(SunError::InvalidInput)` if the elevation is NaN
+    ///  - `Ok(observer)` otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sun_times::{Observer, SunError};
+    ///
+    /// //Latitude and longitude have been swapped
+    /// let observer = Observer::new(-1.48, 153.38, 100.0);
+    /// assert!(observer.is_ok());
+    /// let observer = Observer::new(153.38, -1.48, 100.0);
+    /// assert_eq!(observer, Err(SunError::InvalidLatitude));
+    /// ```
+    pub fn new(latitude: f64, longitude: f64, elevation: f64) -> Result<Observer, SunError> {
+        validate_location(latitude, longitude, elevation)?;
+        Ok(Observer {
+            latitude,
+            longitude,
+            elevation,
+        })
+    }
+
+    /// See [annual_sun_window]
+    pub fn annual_sun_window(&self) -> SunWindow {
+        annual_sun_window(self.latitude)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Observer {
+    /// See [sun_times]
+    pub fn sun_times(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        sun_times(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_julian]
+    pub fn sun_times_julian(&self, date: NaiveDate) -> Option<(JulianDate, JulianDate)> {
+        sun_times_julian(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_precise]
+    pub fn sun_times_precise(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        sun_times_precise(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_checked]
+    pub fn sun_times_checked(
+        &self,
+        date: NaiveDate,
+    ) -> Result<(DateTime<Utc>, DateTime<Utc>), SunError> {
+        sun_times_checked(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_range]
+    #[allow(clippy::type_complexity)]
+    pub fn sun_times_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> impl Iterator<Item = (NaiveDate, Option<(DateTime<Utc>, DateTime<Utc>)>)> {
+        sun_times_range(start, end, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_month]
+    #[cfg(feature = "std")]
+    #[allow(clippy::type_complexity)]
+    pub fn sun_times_month(
+        &self,
+        year: i32,
+        month: u32,
+    ) -> Vec<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        sun_times_month(year, month, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_tz]
+    pub fn sun_times_tz<Tz: TimeZone>(
+        &self,
+        date: NaiveDate,
+        tz: &Tz,
+    ) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
+        sun_times_tz(date, tz, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_local]
+    #[cfg(feature = "tz-lookup")]
+    pub fn sun_times_local(
+        &self,
+        date: NaiveDate,
+    ) -> Option<(DateTime<chrono_tz::Tz>, DateTime<chrono_tz::Tz>)> {
+        sun_times_local(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_local_mean_time]
+    pub fn sun_times_local_mean_time(
+        &self,
+        date: NaiveDate,
+        standard_meridian: f64,
+    ) -> Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+        sun_times_local_mean_time(
+            date,
+            standard_meridian,
+            self.latitude,
+            self.longitude,
+            self.elevation,
+        )
+    }
+
+    /// See [sun_times_with_angle]
+    pub fn sun_times_with_angle(
+        &self,
+        date: NaiveDate,
+        horizon_angle: f64,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        sun_times_with_angle(
+            date,
+            self.latitude,
+            self.longitude,
+            self.elevation,
+            horizon_angle,
+        )
+    }
+
+    /// See [sun_times_ex], with the observer's elevation as that of the terrain
+    pub fn sun_times_ex(
+        &self,
+        date: NaiveDate,
+        eye_height: f64,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        sun_times_ex(
+            date,
+            self.latitude,
+            self.longitude,
+            self.elevation,
+            eye_height,
+        )
+    }
+
+    /// See [daylight_above]. The observer's elevation isn't used
+    pub fn daylight_above(
+        &self,
+        date: NaiveDate,
+        angle: f64,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        daylight_above(date, self.latitude, self.longitude, angle)
+    }
+
+    /// See [shadow_ratio_altitude]
+    pub fn shadow_ratio_altitude(&self, date: NaiveDate, shadow_ratio: f64) -> f64 {
+        shadow_ratio_altitude(date, self.latitude, shadow_ratio)
+    }
+
+    /// See [time_above_thresholds]. The observer's elevation isn't used
+    #[cfg(feature = "std")]
+    pub fn time_above_thresholds(&self, date: NaiveDate, thresholds: &[f64]) -> Vec<Duration> {
+        time_above_thresholds(date, self.latitude, self.longitude, thresholds)
+    }
+
+    /// See [sun_times_numerical]
+    pub fn sun_times_numerical(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        sun_times_numerical(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_with_horizon]
+    pub fn sun_times_with_horizon(
+        &self,
+        date: NaiveDate,
+        horizon: impl Fn(f64) -> f64,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        sun_times_with_horizon(date, self.latitude, self.longitude, horizon)
+    }
+
+    /// See [twilight_times]
+    pub fn twilight_times(
+        &self,
+        date: NaiveDate,
+        kind: Twilight,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        twilight_times(date, self.latitude, self.longitude, self.elevation, kind)
+    }
+
+    /// See [sun_state]
+    pub fn sun_state(&self, date: NaiveDate) -> Option<SunState> {
+        sun_state(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [day_length]
+    pub fn day_length(&self, date: NaiveDate) -> Option<Duration> {
+        day_length(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [solar_noon]
+    pub fn solar_noon(&self, date: NaiveDate) -> Option<DateTime<Utc>> {
+        solar_noon(date, self.longitude)
+    }
+
+    /// See [sun_times_on], where the observer's latitude, longitude, and elevation are on the given body
+    pub fn sun_times_on(
+        &self,
+        body: &CelestialBody,
+        date: NaiveDate,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        sun_times_on(body, date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_debug]
+    pub fn sun_times_debug(&self, date: NaiveDate) -> Option<SunTimesDebug> {
+        sun_times_debug(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_detailed]
+    pub fn sun_times_detailed(&self, date: NaiveDate) -> Option<SunTimesDetailed> {
+        sun_times_detailed(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sun_times_solar_time]
+    pub fn sun_times_solar_time(&self, date: NaiveDate) -> Option<(f64, f64)> {
+        sun_times_solar_time(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [solar_midnight]
+    pub fn solar_midnight(&self, date: NaiveDate) -> Option<DateTime<Utc>> {
+        solar_midnight(date, self.longitude)
+    }
+
+    /// See [solar_day]
+    pub fn solar_day(&self, date: NaiveDate) -> Option<SolarDay> {
+        solar_day(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [solar_events]
+    pub fn solar_events(&self, date: NaiveDate) -> SolarEvents {
+        solar_events(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [next_sunrise]
+    pub fn next_sunrise(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        next_sunrise(after, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [next_sunset]
+    pub fn next_sunset(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        next_sunset(after, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [upcoming_sunrises]
+    #[cfg(feature = "std")]
+    pub fn upcoming_sunrises(&self, from: DateTime<Utc>, n: usize) -> Vec<DateTime<Utc>> {
+        upcoming_sunrises(from, n, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [upcoming_sunsets]
+    #[cfg(feature = "std")]
+    pub fn upcoming_sunsets(&self, from: DateTime<Utc>, n: usize) -> Vec<DateTime<Utc>> {
+        upcoming_sunsets(from, n, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [time_until_sunset]
+    pub fn time_until_sunset(&self, now: DateTime<Utc>) -> Option<Duration> {
+        time_until_sunset(now, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [golden_hour]
+    pub fn golden_hour(&self, date: NaiveDate) -> (Option<Interval>, Option<Interval>) {
+        golden_hour(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [blue_hour]
+    pub fn blue_hour(&self, date: NaiveDate) -> (Option<Interval>, Option<Interval>) {
+        blue_hour(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [season], in the observer's hemisphere (the equator being counted as northern)
+    pub fn season(&self, date: NaiveDate) -> Season {
+        let hemisphere = if self.latitude < 0.0 {
+            Hemisphere::Southern
+        } else {
+            Hemisphere::Northern
+        };
+        season(date, hemisphere)
+    }
+
+    /// See [dawn_duration]
+    pub fn dawn_duration(&self, date: NaiveDate, kind: Twilight) -> Option<Duration> {
+        dawn_duration(date, self.latitude, self.longitude, self.elevation, kind)
+    }
+
+    /// See [dusk_duration]
+    pub fn dusk_duration(&self, date: NaiveDate, kind: Twilight) -> Option<Duration> {
+        dusk_duration(date, self.latitude, self.longitude, self.elevation, kind)
+    }
+
+    /// See [civil_dawn_offset]
+    pub fn civil_dawn_offset(&self, date: NaiveDate) -> Option<Duration> {
+        civil_dawn_offset(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [civil_dusk_offset]
+    pub fn civil_dusk_offset(&self, date: NaiveDate) -> Option<Duration> {
+        civil_dusk_offset(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [has_astronomical_night]
+    pub fn has_astronomical_night(&self, date: NaiveDate) -> bool {
+        has_astronomical_night(date, self.latitude)
+    }
+
+    /// See [astronomical_night_length]. The observer's elevation isn't used
+    pub fn astronomical_night_length(&self, date: NaiveDate) -> Option<Duration> {
+        astronomical_night_length(date, self.latitude, self.longitude)
+    }
+
+    /// See [polar_periods]
+    pub fn polar_periods(&self, year: i32) -> PolarPeriods {
+        polar_periods(year, self.latitude)
+    }
+
+    /// See [max_altitude]
+    pub fn max_altitude(&self, date: NaiveDate) -> f64 {
+        max_altitude(date, self.latitude)
+    }
+
+    /// See [hour_angle_at_altitude]. The observer's elevation isn't used
+    pub fn hour_angle_at_altitude(&self, date: NaiveDate, target_altitude: f64) -> Option<f64> {
+        hour_angle_at_altitude(date, self.latitude, target_altitude)
+    }
+
+    /// See [max_altitude_time]
+    pub fn max_altitude_time(&self, date: NaiveDate) -> Option<DateTime<Utc>> {
+        max_altitude_time(date, self.latitude, self.longitude)
+    }
+
+    /// See [altitude]
+    pub fn altitude(&self, date_time: DateTime<Utc>) -> f64 {
+        altitude(date_time, self.latitude, self.longitude)
+    }
+
+    /// See [apparent_altitude]
+    pub fn apparent_altitude(&self, date_time: DateTime<Utc>) -> f64 {
+        apparent_altitude(date_time, self.latitude, self.longitude)
+    }
+
+    /// See [is_daytime]
+    pub fn is_daytime(&self, date_time: DateTime<Utc>) -> bool {
+        is_daytime(date_time, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [solar_progress]
+    pub fn solar_progress(&self, date_time: DateTime<Utc>) -> Option<f64> {
+        solar_progress(date_time, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [azimuth]
+    pub fn azimuth(&self, date_time: DateTime<Utc>) -> f64 {
+        azimuth(date_time, self.latitude, self.longitude)
+    }
+
+    /// See [sunrise_azimuth]
+    pub fn sunrise_azimuth(&self, date: NaiveDate) -> Option<f64> {
+        sunrise_azimuth(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [sunset_azimuth]
+    pub fn sunset_azimuth(&self, date: NaiveDate) -> Option<f64> {
+        sunset_azimuth(date, self.latitude, self.longitude, self.elevation)
+    }
+
+    /// See [time_at_azimuth]
+    pub fn time_at_azimuth(
+        &self,
+        date: NaiveDate,
+        target_azimuth: f64,
+    ) -> Option<(DateTime<Utc>, f64)> {
+        time_at_azimuth(date, self.latitude, self.longitude, target_azimuth)
+    }
+
+    /// See [solar_zenith_angle]
+    pub fn solar_zenith_angle(&self, date_time: DateTime<Utc>) -> f64 {
+        solar_zenith_angle(date_time, self.latitude, self.longitude)
+    }
+
+    /// See [analemma]
+    #[cfg(feature = "std")]
+    pub fn analemma(&self, year: i32) -> Vec<(NaiveDate, SunPosition)> {
+        analemma(year, self.latitude, self.longitude)
+    }
+
+    /// See [altitude_grid]
+    #[cfg(feature = "std")]
+    pub fn altitude_grid(&self, year: i32, samples_per_day: usize) -> Vec<Vec<f64>> {
+        altitude_grid(year, self.latitude, self.longitude, samples_per_day)
+    }
+
+    /// See [shadow_length]
+    pub fn shadow_length(&self, date_time: DateTime<Utc>, object_height: f64) -> Option<f64> {
+        shadow_length(date_time, self.latitude, self.longitude, object_height)
+    }
+
+    /// See [sun_position]
+    pub fn sun_position(&self, date_time: DateTime<Utc>) -> SunPosition {
+        sun_position(date_time, self.latitude, self.longitude)
+    }
+
+    /// See [sun_vector]
+    pub fn sun_vector(&self, date_time: DateTime<Utc>) -> [f64; 3] {
+        sun_vector(date_time, self.latitude, self.longitude)
+    }
+
+    /// See [sun_positions]
+    #[cfg(feature = "std")]
+    pub fn sun_positions(&self, times: &[DateTime<Utc>]) -> Vec<SunPosition> {
+        sun_positions(times, self.latitude, self.longitude)
+    }
+
+    /// See [sun_track]. The observer's elevation isn't used
+    pub fn sun_track(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        step: Duration,
+    ) -> impl Iterator<Item = (DateTime<Utc>, SunPosition)> {
+        sun_track(start, end, step, self.latitude, self.longitude)
+    }
+
+    /// See [moon_times]
+    pub fn moon_times(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        moon_times(date, self.latitude, self.longitude)
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+
+    #[test]
+    ///Test for https://github.com/Eroc33/sun-times/issues/1
+    fn sunrise_and_sunset_land_on_requested_day() {
+        let date_range =
+            std::iter::successors(Some(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()), |date| {
+                let next = *date + Duration::days(1);
+                if next > NaiveDate::from_ymd_opt(2022, 12, 31).unwrap() {
+                    None
+                } else {
+                    Some(next)
+                }
+            });
+        for date in date_range {
+            let times = super::sun_times(date, 53.38, -1.48, 0.0);
+            assert!(times.is_some());
+            let times = times.unwrap();
+            assert_eq!(date, times.0.naive_utc().date());
+            assert_eq!(date, times.1.naive_utc().date());
+        }
+    }
+
+    #[test]
+    fn twilight_surrounds_sunrise_and_sunset() {
+        use super::Twilight;
+
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let (rise, set) = super::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        let mut previous = (rise, set);
+        for kind in [Twilight::Civil, Twilight::Nautical, Twilight::Astronomical] {
+            let (dawn, dusk) = super::twilight_times(date, 53.38, -1.48, 0.0, kind).unwrap();
+            assert!(dawn < previous.0);
+            assert!(dusk > previous.1);
+            previous = (dawn, dusk);
+        }
+    }
+
+    #[test]
+    fn sun_state_distinguishes_polar_day_and_night() {
+        use super::SunState;
+
+        let midsummer = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        let midwinter = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(
+            super::sun_state(midsummer, 80.0, 0.0, 0.0),
+            Some(SunState::AlwaysUp)
+        );
+        assert_eq!(
+            super::sun_state(midwinter, 80.0, 0.0, 0.0),
+            Some(SunState::AlwaysDown)
+        );
+        assert_eq!(
+            super::sun_state(midsummer, -80.0, 0.0, 0.0),
+            Some(SunState::AlwaysDown)
+        );
+        assert!(matches!(
+            super::sun_state(midsummer, 53.38, -1.48, 0.0),
+            Some(SunState::Rises { .. })
+        ));
+    }
+
+    #[test]
+    fn solar_noon_is_midway_between_sunrise_and_sunset() {
+        let date = NaiveDate::from_ymd_opt(2022, 8, 1).unwrap();
+        let (rise, set) = super::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        let noon = super::solar_noon(date, -1.48).unwrap();
+        assert!(((noon - rise) - (set - noon)).num_seconds().abs() <= 1);
+    }
+
+    #[test]
+    fn day_length_covers_polar_conditions() {
+        let midsummer = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        let midwinter = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(
+            super::day_length(midsummer, 80.0, 0.0, 0.0),
+            Some(Duration::days(1))
+        );
+        assert_eq!(
+            super::day_length(midwinter, 80.0, 0.0, 0.0),
+            Some(Duration::zero())
+        );
+        let summer = super::day_length(midsummer, 53.38, -1.48, 0.0).unwrap();
+        let winter = super::day_length(midwinter, 53.38, -1.48, 0.0).unwrap();
+        assert!(summer > winter);
+    }
+
+    #[test]
+    fn negative_elevation_is_treated_as_sea_level() {
+        //the Dead Sea shore, ~430m below sea level
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let below_sea_level = super::sun_times(date, 31.5, 35.5, -430.0);
+        assert!(below_sea_level.is_some());
+        assert_eq!(below_sea_level, super::sun_times(date, 31.5, 35.5, 0.0));
+    }
+
+    #[test]
+    fn sun_times_tz_land_on_requested_local_day() {
+        use chrono::FixedOffset;
+
+        let zones = [(35.68, 139.69, 9), (-36.85, 174.76, 12), (40.71, -74.0, -5)];
+        for (latitude, longitude, offset_hours) in zones {
+            let tz = FixedOffset::east_opt(offset_hours * 60 * 60).unwrap();
+            for month in 1..=12 {
+                let date = NaiveDate::from_ymd_opt(2022, month, 15).unwrap();
+                let (rise, set) = super::sun_times_tz(date, &tz, latitude, longitude, 0.0).unwrap();
+                assert_eq!(date, rise.date_naive());
+                assert_eq!(date, set.date_naive());
+                assert!(rise < set);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sun_times_serialize_with_named_fields() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let times = super::sun_times(date, 53.38, -1.48, 0.0)
+            .map(super::SunTimes::from)
+            .unwrap();
+        let json = serde_json::to_value(times).unwrap();
+        assert!(json.get("sunrise").is_some());
+        assert!(json.get("sunset").is_some());
+        assert_eq!(times, serde_json::from_value(json).unwrap());
+    }
+
+    #[test]
+    fn golden_hour_follows_blue_hour() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (rise, set) = super::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        let (golden_morning, golden_evening) = super::golden_hour(date, 53.38, -1.48, 0.0);
+        let (blue_morning, blue_evening) = super::blue_hour(date, 53.38, -1.48, 0.0);
+        let (golden_morning, golden_evening) = (golden_morning.unwrap(), golden_evening.unwrap());
+        let (blue_morning, blue_evening) = (blue_morning.unwrap(), blue_evening.unwrap());
+        assert_eq!(blue_morning.end, golden_morning.start);
+        assert_eq!(golden_evening.end, blue_evening.start);
+        assert!(golden_morning.start < rise && rise < golden_morning.end);
+        assert!(golden_evening.start < set && set < golden_evening.end);
+    }
+
+    #[test]
+    fn next_sunrise_skips_polar_night() {
+        use chrono::{TimeZone, Utc};
+
+        let after = Utc.with_ymd_and_hms(2022, 12, 1, 0, 0, 0).unwrap();
+        let sunrise = super::next_sunrise(after, 75.0, 0.0, 0.0).unwrap();
+        let sunset = super::next_sunset(after, 75.0, 0.0, 0.0).unwrap();
+        assert!(sunrise > after);
+        assert!(sunrise.date_naive() > NaiveDate::from_ymd_opt(2022, 12, 31).unwrap());
+        assert!(sunset > sunrise);
+
+        let (rise, set) = super::sun_times(after.date_naive(), 53.38, -1.48, 0.0).unwrap();
+        assert_eq!(super::next_sunrise(after, 53.38, -1.48, 0.0), Some(rise));
+        assert_eq!(super::next_sunset(rise, 53.38, -1.48, 0.0), Some(set));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn upcoming_sunrises_follow_on_from_each_other() {
+        use chrono::{TimeZone, Utc};
+
+        let from = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+        let sunrises = super::upcoming_sunrises(from, 7, 53.38, -1.48, 0.0);
+        let sunsets = super::upcoming_sunsets(from, 7, 53.38, -1.48, 0.0);
+        assert_eq!(sunrises.len(), 7);
+        assert_eq!(sunsets.len(), 7);
+        let mut after = from;
+        for &sunrise in &sunrises {
+            assert_eq!(super::next_sunrise(after, 53.38, -1.48, 0.0), Some(sunrise));
+            after = sunrise;
+        }
+        //the first sunset is that evening's
+        assert_eq!(sunsets[0].date_naive(), from.date_naive());
+        assert!(super::upcoming_sunsets(from, 0, 53.38, -1.48, 0.0).is_empty());
+
+        //at Tromsø the sun doesn't rise from late November until mid January
+        let from = Utc.with_ymd_and_hms(2022, 11, 20, 0, 0, 0).unwrap();
+        let sunrises = super::upcoming_sunrises(from, 14, 69.65, 18.96, 0.0);
+        assert_eq!(sunrises.len(), 14);
+        assert!(sunrises.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(sunrises
+            .windows(2)
+            .any(|pair| (pair[1] - pair[0]).num_days() > 40));
+
+        //while at the pole it never does on any single day
+        assert!(super::upcoming_sunrises(from, 1, 90.0, 0.0, 0.0).is_empty());
+    }
+
+    #[test]
+    fn altitude_at_solar_noon_matches_declination() {
+        for (latitude, longitude) in [
+            (53.38, -1.48),
+            (-33.87, 151.21),
+            (0.0, -78.0),
+            (64.15, -21.94),
+        ] {
+            for month in 1..=12 {
+                let date = NaiveDate::from_ymd_opt(2022, month, 15).unwrap();
+                let noon = super::solar_noon(date, longitude).unwrap();
+                let expected = 90.0 - (latitude - super::declination(date)).abs();
+                let position = super::sun_position(noon, latitude, longitude);
+                assert!((position.altitude - expected).abs() < 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn refraction_lifts_the_sun_at_sunrise() {
+        assert!((super::refraction(0.0) - 0.48).abs() < 0.02);
+        assert!(super::refraction(90.0).abs() < 0.01);
+        assert!((super::refraction(-0.833) - super::HORIZON_REFRACTION).abs() < 0.01);
+        assert!((super::refraction(-5.0) - super::HORIZON_REFRACTION).abs() < 0.01);
+
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (rise, _) = super::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        let apparent = super::apparent_altitude(rise, 53.38, -1.48);
+        assert!((apparent - -0.26).abs() < 0.05);
+    }
+
+    #[test]
+    fn sun_times_range_covers_every_date() {
+        let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+        let mut days = 0;
+        for (date, times) in super::sun_times_range(start, end, 53.38, -1.48, 0.0) {
+            assert_eq!(times, super::sun_times(date, 53.38, -1.48, 0.0));
+            days += 1;
+        }
+        assert_eq!(days, 365);
+    }
+
+    #[test]
+    fn sun_times_checked_explains_failures() {
+        use super::SunError;
+
+        let summer = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        let winter = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(
+            super::sun_times_checked(summer, 53.38, -1.48, 100.0).ok(),
+            super::sun_times(summer, 53.38, -1.48, 100.0)
+        );
+        assert_eq!(
+            super::sun_times_checked(summer, 69.65, 18.96, 0.0),
+            Err(SunError::AlwaysUp)
+        );
+        assert_eq!(
+            super::sun_times_checked(winter, 69.65, 18.96, 0.0),
+            Err(SunError::AlwaysDown)
+        );
+        assert_eq!(
+            super::sun_times_checked(summer, 200.0, -1.48, 0.0),
+            Err(SunError::InvalidLatitude)
+        );
+        assert_eq!(
+            super::sun_times_checked(summer, f64::NAN, -1.48, 0.0),
+            Err(SunError::InvalidLatitude)
+        );
+        assert_eq!(
+            super::sun_times_checked(summer, 53.38, f64::NAN, 0.0),
+            Err(SunError::InvalidLongitude)
+        );
+        assert_eq!(
+            super::sun_times_checked(summer, 53.38, -1.48, f64::NAN),
+            Err(SunError::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn observer_new_validates_coordinates() {
+        use super::{Observer, SunError};
+
+        assert!(Observer::new(53.38, -1.48, 100.0).is_ok());
+        assert!(Observer::new(-90.0, 180.0, 0.0).is_ok());
+        assert_eq!(
+            Observer::new(90.5, -1.48, 100.0),
+            Err(SunError::InvalidLatitude)
+        );
+        assert_eq!(
+            Observer::new(53.38, -181.0, 100.0),
+            Err(SunError::InvalidLongitude)
+        );
+        assert_eq!(
+            Observer::new(53.38, -1.48, f64::NAN),
+            Err(SunError::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn shadow_length_matches_altitude() {
+        use chrono::{TimeZone, Utc};
+
+        let noon = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+        let altitude = super::altitude(noon, 53.38, -1.48);
+        let zenith_angle = super::solar_zenith_angle(noon, 53.38, -1.48);
+        assert!((altitude + zenith_angle - 90.0).abs() < 1e-9);
+
+        //at noon in summer the sun is high, so the shadow is shorter than the object
+        let length = super::shadow_length(noon, 53.38, -1.48, 2.0).unwrap();
+        assert!(length > 0.0 && length < 2.0);
+        let expected = 2.0 * zenith_angle.to_radians().tan();
+        assert!((length - expected).abs() < 1e-9);
+
+        let midnight = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(super::shadow_length(midnight, 53.38, -1.48, 2.0), None);
+    }
+
+    #[test]
+    fn day_solar_params_match_altitude() {
+        use super::DaySolarParams;
+
+        for month in 1..=12 {
+            let date = NaiveDate::from_ymd_opt(2022, month, 15).unwrap();
+            let params = DaySolarParams::for_date(date);
+            for minute in (0..24 * 60).step_by(7) {
+                let date_time =
+                    date.and_hms_opt(0, 0, 0).unwrap().and_utc() + Duration::minutes(minute);
+                let position = super::sun_position(date_time, 53.38, -1.48);
+                let interpolated = params.sun_position(date_time, 53.38, -1.48);
+                assert!((position.altitude - interpolated.altitude).abs() < 0.002);
+                let azimuth_difference =
+                    (position.azimuth - interpolated.azimuth + 540.0) % 360.0 - 180.0;
+                assert!(azimuth_difference.abs() < 0.002);
+            }
+        }
+    }
+
+    #[test]
+    fn right_ascension_follows_the_seasons() {
+        let equinox = NaiveDate::from_ymd_opt(2022, 3, 20).unwrap();
+        let equinox_right_ascension = super::right_ascension(equinox);
+        assert!(!(1.0..=359.0).contains(&equinox_right_ascension));
+        for (month, expected) in [(6, 90.0), (9, 180.0), (12, 270.0)] {
+            let date = NaiveDate::from_ymd_opt(2022, month, 21).unwrap();
+            assert!((super::right_ascension(date) - expected).abs() < 2.0);
+        }
+    }
+
+    #[test]
+    fn max_altitude_matches_altitude_at_solar_noon() {
+        for (latitude, longitude) in [(53.38, -1.48), (-33.87, 151.21), (0.0, 0.0), (78.22, 15.65)]
+        {
+            for month in 1..=12 {
+                let date = NaiveDate::from_ymd_opt(2022, month, 15).unwrap();
+                let noon = super::solar_noon(date, longitude).unwrap();
+                let altitude = super::altitude(noon, latitude, longitude);
+                assert!((super::max_altitude(date, latitude) - altitude).abs() < 0.5);
+            }
+        }
+    }
+
+    #[test]
+    ///Test for https://github.com/Eroc33/sun-times/issues/1 at longitudes further from Greenwich
+    fn sunrise_and_sunset_land_on_requested_day_at_all_longitudes() {
+        let start = NaiveDate::from_ymd_opt(2022, 9, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+        for longitude in (-180..=180).step_by(15) {
+            for latitude in [-40.0, 0.0, 53.38] {
+                for date in start.iter_days().take_while(|&date| date <= end) {
+                    let (rise, set) =
+                        super::sun_times(date, latitude, f64::from(longitude), 0.0).unwrap();
+                    for event in [rise, set] {
+                        if event.date_naive() != date {
+                            //the event drifted across midnight, skipping this date, so must be just either side of it
+                            let start_of_day = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+                            let end_of_day = start_of_day + Duration::days(1);
+                            assert!(event > start_of_day - Duration::minutes(5));
+                            assert!(event < end_of_day + Duration::minutes(5));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solar_day_matches_separate_calculations() {
+        use super::SunState;
+
+        for longitude in [-120.0, -1.48, 139.69] {
+            let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+            let day = super::solar_day(date, 53.38, longitude, 100.0).unwrap();
+            assert_eq!(
+                super::sun_state(date, 53.38, longitude, 100.0),
+                Some(SunState::Rises {
+                    rise: day.sunrise,
+                    set: day.sunset
+                })
+            );
+            assert_eq!(super::solar_noon(date, longitude), Some(day.solar_noon));
+            assert_eq!(day.solar_noon - day.solar_midnight, Duration::hours(12));
+        }
+
+        let polar_night = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(super::solar_day(polar_night, 69.65, 18.96, 0.0), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn sun_times_month_covers_every_day() {
+        let times = super::sun_times_month(2024, 2, 53.38, -1.48, 100.0);
+        assert_eq!(times.len(), 29);
+        for (index, times) in times.into_iter().enumerate() {
+            let date = NaiveDate::from_ymd_opt(2024, 2, index as u32 + 1).unwrap();
+            assert_eq!(times, super::sun_times(date, 53.38, -1.48, 100.0));
+        }
+        assert!(super::sun_times_month(2024, 13, 53.38, -1.48, 100.0).is_empty());
+    }
+
+    #[test]
+    fn solar_time_is_offset_from_solar_noon() {
+        for day in 1..=30 {
+            let date = NaiveDate::from_ymd_opt(2022, 6, day).unwrap();
+            let (rise, set) = super::sun_times_solar_time(date, 53.38, -1.48, 100.0).unwrap();
+            let solar_day = super::solar_day(date, 53.38, -1.48, 100.0).unwrap();
+            let hours_before_noon =
+                (solar_day.solar_noon - solar_day.sunrise).num_seconds() as f64 / 3600.0;
+            assert!((12.0 - rise - hours_before_noon).abs() < 0.001);
+            assert!((rise + set - 24.0).abs() < 1e-9);
+        }
+        let date = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(super::sun_times_solar_time(date, 69.65, 18.96, 0.0), None);
+    }
+
+    #[test]
+    fn equinoxes_and_solstices_match_published_times() {
+        use chrono::{TimeZone, Utc};
+
+        use super::{next_equinox, next_solstice};
+
+        //from https://aa.usno.navy.mil/data/Earth_Seasons
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        let march_equinox = next_equinox(start).unwrap();
+        let june_solstice = next_solstice(start).unwrap();
+        let september_equinox = next_equinox(march_equinox + Duration::days(1)).unwrap();
+        let december_solstice = next_solstice(june_solstice + Duration::days(1)).unwrap();
+        for (calculated, expected) in [
+            (march_equinox, Utc.with_ymd_and_hms(2022, 3, 20, 15, 33, 0)),
+            (june_solstice, Utc.with_ymd_and_hms(2022, 6, 21, 9, 14, 0)),
+            (
+                september_equinox,
+                Utc.with_ymd_and_hms(2022, 9, 23, 1, 4, 0),
+            ),
+            (
+                december_solstice,
+                Utc.with_ymd_and_hms(2022, 12, 21, 21, 48, 0),
+            ),
+        ] {
+            assert!((calculated - expected.unwrap()).num_minutes().abs() < 15);
+        }
+    }
+
+    #[test]
+    fn seasons_change_on_the_day_of_the_equinox_or_solstice() {
+        use super::{season, Hemisphere, Season};
+
+        for (month, day, northern, southern) in [
+            (3, 19, Season::Winter, Season::Summer),
+            (3, 20, Season::Spring, Season::Autumn),
+            (6, 21, Season::Summer, Season::Winter),
+            (9, 23, Season::Autumn, Season::Spring),
+            (12, 21, Season::Winter, Season::Summer),
+        ] {
+            let date = NaiveDate::from_ymd_opt(2022, month, day).unwrap();
+            assert_eq!(season(date, Hemisphere::Northern), northern);
+            assert_eq!(season(date, Hemisphere::Southern), southern);
+        }
+    }
+
+    #[test]
+    fn detailed_sun_times_are_symmetric_about_solar_noon() {
+        for day in 1..=30 {
+            let date = NaiveDate::from_ymd_opt(2022, 6, day).unwrap();
+            let times = super::sun_times_detailed(date, 53.38, -1.48, 100.0).unwrap();
+            let solar_day = super::solar_day(date, 53.38, -1.48, 100.0).unwrap();
+            assert_eq!(times.sunrise, solar_day.sunrise);
+            assert_eq!(times.sunset, solar_day.sunset);
+            assert_eq!(times.solar_noon, solar_day.solar_noon);
+
+            //15° per hour is 4 minutes per degree
+            let hour_angle_seconds = (times.hour_angle * 4.0 * 60.0).round() as i64;
+            assert!(
+                ((times.solar_noon - times.sunrise).num_seconds() - hour_angle_seconds).abs() <= 1
+            );
+            assert!(
+                ((times.sunset - times.solar_noon).num_seconds() - hour_angle_seconds).abs() <= 1
+            );
+        }
+    }
+
+    #[test]
+    fn twilight_durations_run_from_twilight_to_sunrise_and_sunset() {
+        use super::{dawn_duration, dusk_duration, twilight_times, Twilight};
+
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let (sunrise, sunset) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+        let mut previous = (Duration::zero(), Duration::zero());
+        for kind in [Twilight::Civil, Twilight::Nautical, Twilight::Astronomical] {
+            let (dawn, dusk) = twilight_times(date, 53.38, -1.48, 100.0, kind).unwrap();
+            let dawn_duration = dawn_duration(date, 53.38, -1.48, 100.0, kind).unwrap();
+            let dusk_duration = dusk_duration(date, 53.38, -1.48, 100.0, kind).unwrap();
+            assert_eq!(dawn_duration, sunrise - dawn);
+            assert_eq!(dusk_duration, dusk - sunset);
+            //deeper twilights start earlier and end later
+            assert!(dawn_duration > previous.0 && dusk_duration > previous.1);
+            previous = (dawn_duration, dusk_duration);
+        }
+
+        //the sun doesn't get 18° below the horizon in midsummer in Sheffield
+        let midsummer = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        assert_eq!(
+            dawn_duration(midsummer, 53.38, -1.48, 100.0, Twilight::Astronomical),
+            None
+        );
+        assert_eq!(
+            dusk_duration(midsummer, 53.38, -1.48, 100.0, Twilight::Astronomical),
+            None
+        );
+    }
+
+    #[test]
+    fn solar_midnight_is_between_solar_noons() {
+        for longitude in [-179.0, -90.0, -1.48, 90.0, 179.0] {
+            let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+            let midnight = super::solar_midnight(date, longitude).unwrap();
+            let noon = super::solar_noon(date, longitude).unwrap();
+            let next_noon = super::solar_noon(date.succ_opt().unwrap(), longitude).unwrap();
+            assert!(noon < midnight && midnight < next_noon);
+            assert!(
+                ((midnight - noon) - (next_noon - midnight))
+                    .num_seconds()
+                    .abs()
+                    < 60
+            );
+        }
+    }
+
+    #[test]
+    fn delta_t_is_continuous_and_matches_published_values() {
+        let at_year = |year: f64| {
+            let julian_date = super::JulianDate(super::JAN_2000.0 + (year - 2000.0) * 365.25);
+            super::delta_t(julian_date).0 * super::SECONDS_PER_DAY as f64
+        };
+
+        //from https://eclipse.gsfc.nasa.gov/SEhelp/deltat2004.html
+        for (year, expected) in [(1900.0, -2.8), (1950.0, 29.1), (2000.0, 63.8)] {
+            assert!((at_year(year) - expected).abs() < 0.5);
+        }
+        for boundary in [
+            -500.0, 500.0, 1600.0, 1700.0, 1800.0, 1860.0, 1900.0, 1920.0, 1941.0, 1961.0, 1986.0,
+            2005.0, 2050.0, 2150.0,
+        ] {
+            assert!((at_year(boundary - 1e-6) - at_year(boundary)).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn daytime_is_between_sunrise_and_sunset() {
+        use super::is_daytime;
+
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let (rise, set) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+        //the altitude and the sunrise equation agree to within a few minutes
+        let minutes = Duration::minutes(5);
+        assert!(!is_daytime(rise - minutes, 53.38, -1.48, 100.0));
+        assert!(is_daytime(rise + minutes, 53.38, -1.48, 100.0));
+        assert!(is_daytime(set - minutes, 53.38, -1.48, 100.0));
+        assert!(!is_daytime(set + minutes, 53.38, -1.48, 100.0));
+
+        //midnight in polar day, and midday in polar night
+        let midsummer = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        let midwinter = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        let at = |date: NaiveDate, hour| date.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+        assert!(is_daytime(at(midsummer, 0), 69.65, 18.96, 0.0));
+        assert!(!is_daytime(at(midwinter, 11), 69.65, 18.96, 0.0));
+    }
 
-    let greenwich_sidereal_time = mean_solar_time + 0.0;
-    let local_sideral_time = greenwich_sidereal_time
-        + (date_time.time().hour() as f64
-            + (date_time.time().minute() as f64 / 60.0)
-            + (date_time.time().second() as f64 / 60.0 * 60.0))
-            * 15.0
-        + longitude.to_degrees();
-    let local_hour_angle = local_sideral_time - right_ascension;
+    #[test]
+    fn sun_rises_in_the_east_and_sets_in_the_west() {
+        use super::{sunrise_azimuth, sunset_azimuth};
 
-    let sin_altitude = (latitude.to_radians().sin() * declination.sin())
-        + (latitude.to_radians().cos() * declination.cos() * local_hour_angle.to_radians().cos());
-    sin_altitude.asin().to_degrees()
-}
+        //due east and west at the equinox, north of them in summer, and south of them in winter
+        for (month, day, north_of_east) in [(3, 20, 0.0), (6, 21, 44.0), (12, 21, -40.0)] {
+            let date = NaiveDate::from_ymd_opt(2022, month, day).unwrap();
+            let rise = sunrise_azimuth(date, 53.38, -1.48, 100.0).unwrap();
+            let set = sunset_azimuth(date, 53.38, -1.48, 100.0).unwrap();
+            assert!((90.0 - north_of_east - rise).abs() < 3.0);
+            assert!((270.0 + north_of_east - set).abs() < 3.0);
+        }
+        let polar_night = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(sunrise_azimuth(polar_night, 69.65, 18.96, 0.0), None);
+    }
 
-#[cfg(test)]
-mod tests {
-    use chrono::{Duration, NaiveDate};
+    #[test]
+    fn horizon_profile_delays_sunrise_only_where_raised() {
+        use super::{sun_times_with_horizon, SunTimesBuilder};
+
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let flat = sun_times_with_horizon(date, 53.38, -1.48, |_| 2.0).unwrap();
+        let (builder_rise, builder_set) = SunTimesBuilder::new()
+            .horizon_angle(2.0)
+            .calculate(date, 53.38, -1.48)
+            .unwrap();
+        assert_eq!(flat, (builder_rise, builder_set));
+
+        //hills to the east, which the sun rises behind, but an open horizon to the west
+        let (rise, set) =
+            sun_times_with_horizon(
+                date,
+                53.38,
+                -1.48,
+                |azimuth| if azimuth < 180.0 { 5.0 } else { 0.0 },
+            )
+            .unwrap();
+        let (flat_rise, flat_set) = super::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        assert!(rise > flat_rise + Duration::minutes(30));
+        assert_eq!(set, flat_set);
+        //the altitude and the sunrise equation agree to within a few minutes, i.e. a few tenths of a degree
+        assert!(
+            (super::altitude(rise, 53.38, -1.48) - (5.0 + super::SUNRISE_SUNSET_ANGLE)).abs() < 0.5
+        );
+
+        assert_eq!(sun_times_with_horizon(date, 53.38, -1.48, |_| 80.0), None);
+    }
 
     #[test]
-    ///Test for https://github.com/Eroc33/sun-times/issues/1
-    fn sunrise_and_sunset_land_on_requested_day() {
-        let date_range =
-            std::iter::successors(Some(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()), |date| {
-                let next = *date + Duration::days(1);
-                if next > NaiveDate::from_ymd_opt(2022, 12, 31).unwrap() {
-                    None
-                } else {
-                    Some(next)
+    fn numerical_sun_times_agree_with_sun_times() {
+        for day in 1..=30 {
+            let date = NaiveDate::from_ymd_opt(2022, 4, day).unwrap();
+            let (rise, set) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+            let (numerical_rise, numerical_set) =
+                super::sun_times_numerical(date, 53.38, -1.48, 100.0).unwrap();
+            assert!((rise - numerical_rise).num_minutes().abs() < 5);
+            assert!((set - numerical_set).num_minutes().abs() < 5);
+            for time in [numerical_rise, numerical_set] {
+                let altitude = super::altitude(time, 53.38, -1.48);
+                assert!((altitude - super::sunrise_sunset_altitude(100.0)).abs() < 0.01);
+            }
+        }
+
+        let polar_night = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(
+            super::sun_times_numerical(polar_night, 69.65, 18.96, 0.0),
+            None
+        );
+    }
+
+    #[test]
+    fn julian_sun_times_round_to_sun_times() {
+        for day in 1..=30 {
+            let date = NaiveDate::from_ymd_opt(2022, 6, day).unwrap();
+            let (rise, set) = super::sun_times_julian(date, 53.38, -1.48, 100.0).unwrap();
+            assert_eq!(
+                Some((rise.to_datetime().unwrap(), set.to_datetime().unwrap())),
+                super::sun_times(date, 53.38, -1.48, 100.0)
+            );
+        }
+    }
+
+    #[test]
+    fn daylight_above_an_angle_is_within_the_day() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (rise, set) = super::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        let (start, end) = super::daylight_above(date, 53.38, -1.48, 10.0).unwrap();
+        assert!(rise < start && end < set);
+        //the altitude and the sunrise equation agree to within a few minutes, i.e. a few tenths of a degree
+        for time in [start, end] {
+            assert!((super::altitude(time, 53.38, -1.48) - 10.0).abs() < 0.5);
+        }
+
+        //the sun only gets ~13° up at midwinter
+        let midwinter = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(super::daylight_above(midwinter, 53.38, -1.48, 20.0), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn analemma_spans_the_declination_and_equation_of_time() {
+        let analemma = super::analemma(2022, 53.38, -1.48);
+        assert_eq!(analemma.len(), 365);
+        assert_eq!(analemma[0].0, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+
+        let altitudes = analemma.iter().map(|(_, position)| position.altitude);
+        let highest = altitudes.clone().fold(f64::MIN, f64::max);
+        let lowest = altitudes.fold(f64::MAX, f64::min);
+        assert!((highest - (90.0 - 53.38 + 23.44)).abs() < 0.5);
+        assert!((lowest - (90.0 - 53.38 - 23.44)).abs() < 0.5);
+
+        //the sun is up to ~16 minutes (i.e. ~4°) away from the meridian at mean noon
+        for (_, position) in &analemma {
+            assert!((position.azimuth - 180.0).abs() < 8.0);
+        }
+        assert!(super::analemma(i32::MAX, 53.38, -1.48).is_empty());
+    }
+
+    #[test]
+    fn midnight_sun_and_polar_night_start_on_known_dates() {
+        //whether the sun stays above or below the horizon for the whole of the given day, at 80°N
+        let stays = |date: NaiveDate, above: bool| {
+            let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+            (0..24 * 6).all(|step| {
+                let altitude = super::altitude(start + Duration::minutes(10 * step), 80.0, 0.0);
+                (altitude > super::SUNRISE_SUNSET_ANGLE) == above
+            })
+        };
+        let first_day = |from: (u32, u32), above: bool| {
+            NaiveDate::from_ymd_opt(2022, from.0, from.1)
+                .unwrap()
+                .iter_days()
+                .find(|&date| stays(date, above))
+                .unwrap()
+        };
+
+        //at 80°N the midnight sun starts in mid April, and polar night in late October
+        for (from, above, expected) in [((3, 1), true, (4, 14)), ((9, 1), false, (10, 22))] {
+            let expected = NaiveDate::from_ymd_opt(2022, expected.0, expected.1).unwrap();
+            assert!((first_day(from, above) - expected).num_days().abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn polar_periods_span_the_year_end_where_needed() {
+        use chrono::Datelike;
+
+        use super::polar_periods;
+
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let near = |period: Option<(NaiveDate, NaiveDate)>, start: NaiveDate, end: NaiveDate| {
+            let (period_start, period_end) = period.unwrap();
+            assert!((period_start - start).num_days().abs() <= 2);
+            assert!((period_end - end).num_days().abs() <= 2);
+        };
+
+        let arctic = polar_periods(2022, 80.0);
+        near(arctic.midnight_sun, date(2022, 4, 14), date(2022, 8, 28));
+        near(arctic.polar_night, date(2022, 10, 22), date(2023, 2, 20));
+
+        let antarctic = polar_periods(2022, -80.0);
+        near(
+            antarctic.midnight_sun,
+            date(2022, 10, 17),
+            date(2023, 2, 25),
+        );
+        near(antarctic.polar_night, date(2022, 4, 19), date(2022, 8, 24));
+
+        let north_pole = polar_periods(2022, 90.0);
+        assert_eq!(north_pole.polar_night.unwrap().0.year(), 2022);
+
+        let sheffield = polar_periods(2022, 53.38);
+        assert_eq!(sheffield.midnight_sun, None);
+        assert_eq!(sheffield.polar_night, None);
+    }
+
+    #[test]
+    fn sun_times_display_as_local_times() {
+        use chrono::{FixedOffset, TimeZone, Utc};
+
+        let times = super::SunTimes {
+            sunrise: Utc.with_ymd_and_hms(2022, 6, 1, 3, 47, 12).unwrap(),
+            sunset: Utc.with_ymd_and_hms(2022, 6, 1, 20, 24, 50).unwrap(),
+        };
+        assert_eq!(times.to_string(), "sunrise 03:47, sunset 20:24");
+        let bst = FixedOffset::east_opt(3600).unwrap();
+        assert_eq!(
+            times.format_local(&bst).to_string(),
+            "sunrise 04:47, sunset 21:24"
+        );
+    }
+
+    #[test]
+    fn local_mean_time_is_centred_on_noon_at_the_meridian() {
+        use chrono::Timelike;
+
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (rise, set) = super::sun_times_local_mean_time(date, -75.0, 40.0, -75.0, 0.0).unwrap();
+        assert_eq!(rise.offset().local_minus_utc(), -5 * 60 * 60);
+        assert_eq!(rise.date_naive(), date);
+        //solar noon differs from mean noon by the equation of time, which is only a couple of minutes on this date
+        let noon = rise + (set - rise) / 2;
+        let minutes = (noon.hour() * 60 + noon.minute()) as i64;
+        assert!((minutes - 12 * 60).abs() <= 5);
+
+        //a place a degree east of the meridian sees the sun 4 minutes earlier
+        let (east_rise, _) =
+            super::sun_times_local_mean_time(date, -75.0, 40.0, -74.0, 0.0).unwrap();
+        assert!(((rise - east_rise).num_seconds() - 240).abs() <= 10);
+
+        assert_eq!(
+            super::sun_times_local_mean_time(date, 200.0, 40.0, -75.0, 0.0),
+            None
+        );
+    }
+
+    #[test]
+    fn far_dates_are_on_the_requested_date_or_out_of_range() {
+        use super::{JulianDate, SunError};
+
+        for date in [
+            NaiveDate::from_ymd_opt(1600, 6, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2300, 6, 1).unwrap(),
+            NaiveDate::from_ymd_opt(-3000, 6, 1).unwrap(),
+            NaiveDate::from_ymd_opt(100000, 6, 1).unwrap(),
+            NaiveDate::MIN,
+            NaiveDate::MAX,
+        ] {
+            match super::sun_times_checked(date, 53.38, -1.48, 0.0) {
+                Ok((rise, set)) => {
+                    assert_eq!(rise.date_naive(), date);
+                    assert_eq!(set.date_naive(), date);
                 }
-            });
-        for date in date_range {
-            let times = super::sun_times(date, 53.38, -1.48, 0.0);
-            assert!(times.is_some());
-            let times = times.unwrap();
-            assert_eq!(date, times.0.naive_utc().date());
-            assert_eq!(date, times.1.naive_utc().date());
+                Err(error) => assert_eq!(error, SunError::TimestampOutOfRange),
+            }
+        }
+        assert_eq!(JulianDate::new(f64::NAN).to_datetime(), None);
+    }
+
+    #[test]
+    fn time_at_azimuth_finds_manhattanhenge() {
+        use chrono::{TimeZone, Utc};
+
+        //the sun sets along Manhattan's streets at around 8:20pm EDT on the 11th of July
+        let date = NaiveDate::from_ymd_opt(2022, 7, 12).unwrap();
+        let (time, altitude) = super::time_at_azimuth(date, 40.78, -73.97, 299.0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2022, 7, 12, 0, 20, 0).unwrap();
+        assert!((time - expected).num_minutes().abs() <= 20);
+        assert!((-2.0..3.0).contains(&altitude));
+        assert!((super::azimuth(time, 40.78, -73.97) - 299.0).abs() < 0.1);
+
+        //due south is very close to solar noon
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (time, altitude) = super::time_at_azimuth(date, 53.38, -1.48, 180.0).unwrap();
+        let noon = super::solar_noon(date, -1.48).unwrap();
+        assert!((time - noon).num_minutes().abs() <= 2);
+        assert!((altitude - super::max_altitude(date, 53.38)).abs() < 0.5);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn sun_positions_match_sun_position() {
+        use chrono::{TimeZone, Utc};
+
+        let start = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        let times: Vec<_> = (0..48).map(|hour| start + Duration::hours(hour)).collect();
+        let positions = super::sun_positions(&times, 53.38, -1.48);
+        assert_eq!(positions.len(), times.len());
+        for (&time, position) in times.iter().zip(positions) {
+            let expected = super::sun_position(time, 53.38, -1.48);
+            assert!((position.altitude - expected.altitude).abs() < 0.002);
+            let azimuth_difference = (position.azimuth - expected.azimuth + 540.0) % 360.0 - 180.0;
+            assert!(azimuth_difference.abs() < 0.002);
+        }
+    }
+
+    #[test]
+    fn ecliptic_longitude_follows_from_the_mean_anomaly() {
+        for month in 1..=12 {
+            let date = NaiveDate::from_ymd_opt(2022, month, 1).unwrap();
+            let mean_anomaly = super::solar_mean_anomaly(date);
+            assert!((0.0..360.0).contains(&mean_anomaly));
+            //the equation of the centre is at most ~2°
+            let days_since_2000 = (super::JulianDate::from_date(date) - super::JAN_2000).days();
+            let perihelion = 102.9372 + 0.0000470935 * days_since_2000;
+            let expected = (mean_anomaly + 180.0 + perihelion) % 360.0;
+            let difference = (super::ecliptic_longitude(date) - expected + 540.0) % 360.0 - 180.0;
+            assert!(difference.abs() < 2.0);
+            //and differs from the apparent longitude used for the seasons by just the aberration and nutation, at noon
+            //as the sunrise equation uses
+            let noon = super::JulianDate(super::JulianDate::from_date(date).0 + 0.5);
+            let precise = super::apparent_ecliptic_longitude(noon);
+            let difference = (super::ecliptic_longitude(date) - precise + 540.0) % 360.0 - 180.0;
+            assert!(difference.abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn time_until_sunset_counts_down_to_the_next_sunset() {
+        use chrono::{TimeZone, Utc};
+
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (_, sunset) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+        let midday = Utc.with_ymd_and_hms(2022, 6, 1, 12, 0, 0).unwrap();
+        assert_eq!(
+            super::time_until_sunset(midday, 53.38, -1.48, 100.0),
+            Some(sunset - midday)
+        );
+
+        //once the sun has set, it's tomorrow's sunset
+        let (_, tomorrow) =
+            super::sun_times(date.succ_opt().unwrap(), 53.38, -1.48, 100.0).unwrap();
+        let after_sunset = sunset + Duration::minutes(1);
+        assert_eq!(
+            super::time_until_sunset(after_sunset, 53.38, -1.48, 100.0),
+            Some(tomorrow - after_sunset)
+        );
+
+        //in the midnight sun it's weeks away
+        let midsummer = Utc.with_ymd_and_hms(2022, 6, 21, 12, 0, 0).unwrap();
+        let remaining = super::time_until_sunset(midsummer, 69.65, 18.96, 0.0).unwrap();
+        assert!(remaining > Duration::days(7));
+    }
+
+    #[test]
+    fn astronomical_night_is_missing_around_midsummer_at_high_latitudes() {
+        use super::has_astronomical_night;
+
+        let midsummer = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        let midwinter = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        //at midsummer the sun gets 90 - latitude - 23.44 degrees below the horizon at midnight
+        assert!(has_astronomical_night(midsummer, 45.0));
+        assert!(!has_astronomical_night(midsummer, 51.5));
+        assert!(!has_astronomical_night(midsummer, 70.0));
+        assert!(has_astronomical_night(midwinter, 51.5));
+        //even in polar night
+        assert!(has_astronomical_night(midwinter, 80.0));
+        assert!(!has_astronomical_night(midwinter, -51.5));
+    }
+
+    #[test]
+    fn fractional_hours_count_from_local_midnight() {
+        use chrono::{FixedOffset, TimeZone, Utc};
+
+        let time = Utc.with_ymd_and_hms(2022, 6, 1, 6, 23, 24).unwrap();
+        assert!((super::as_fractional_hours(time, &Utc) - 6.39).abs() < 1e-9);
+        let bst = FixedOffset::east_opt(3600).unwrap();
+        assert!((super::as_fractional_hours(time, &bst) - 7.39).abs() < 1e-9);
+        let pdt = FixedOffset::west_opt(7 * 3600).unwrap();
+        assert!((super::as_fractional_hours(time, &pdt) - 23.39).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sun_times_ex_separates_terrain_from_eye_height() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let sea_level = super::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        assert_eq!(
+            super::sun_times_ex(date, 53.38, -1.48, 0.0, 0.0),
+            Some(sea_level)
+        );
+
+        //looking down from a tower the sun rises earlier, though not by as much as the uncorrected dip makes out
+        let (tower_rise, tower_set) = super::sun_times_ex(date, 53.38, -1.48, 0.0, 100.0).unwrap();
+        let (lumped_rise, lumped_set) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+        assert!(lumped_rise < tower_rise && tower_rise < sea_level.0);
+        assert!(sea_level.1 < tower_set && tower_set < lumped_set);
+
+        //while on a plateau there's less refraction, so the sun rises a little later
+        let (plateau_rise, plateau_set) =
+            super::sun_times_ex(date, 53.38, -1.48, 3000.0, 0.0).unwrap();
+        assert!(sea_level.0 < plateau_rise);
+        assert!(plateau_set < sea_level.1);
+        assert!((plateau_rise - sea_level.0).num_minutes() < 5);
+    }
+
+    #[test]
+    fn times_near_the_antimeridian_land_on_the_requested_date() {
+        use chrono::FixedOffset;
+
+        //Fiji, Kiritimati, Samoa, and either side of the antimeridian itself
+        let places = [
+            (-18.14, 178.44),
+            (1.87, -157.4),
+            (-13.83, -171.76),
+            (52.0, 179.9),
+            (-45.0, -179.9),
+        ];
+        let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        for date in start.iter_days().take(365) {
+            for (latitude, longitude) in places {
+                let (rise, set) = super::sun_times(date, latitude, longitude, 0.0).unwrap();
+                assert_eq!(rise.date_naive(), date);
+                assert_eq!(set.date_naive(), date);
+            }
+            //±180° are the same place
+            assert_eq!(
+                super::sun_times(date, 10.0, 180.0, 0.0),
+                super::sun_times(date, 10.0, -180.0, 0.0)
+            );
+
+            //and in Fiji's own timezone, the times are those of the local day
+            let fiji = FixedOffset::east_opt(12 * 60 * 60).unwrap();
+            let (rise, set) = super::sun_times_tz(date, &fiji, -18.14, 178.44, 0.0).unwrap();
+            assert_eq!(rise.date_naive(), date);
+            assert_eq!(set.date_naive(), date);
+            assert!(rise < set);
+        }
+    }
+
+    #[test]
+    fn sun_vector_points_towards_the_sun() {
+        use chrono::{TimeZone, Utc};
+
+        let start = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        for hour in 0..24 {
+            let date_time = start + Duration::hours(hour);
+            let [east, north, up] = super::sun_vector(date_time, 53.38, -1.48);
+            assert!((east * east + north * north + up * up - 1.0).abs() < 1e-9);
+            let position = super::sun_position(date_time, 53.38, -1.48);
+            assert!((up.asin().to_degrees() - position.altitude).abs() < 1e-9);
+            let azimuth = east.atan2(north).to_degrees().rem_euclid(360.0);
+            assert!((azimuth - position.azimuth).abs() < 1e-6);
+        }
+
+        //around midday in the northern hemisphere the sun is to the south
+        let noon = super::solar_noon(NaiveDate::from_ymd_opt(2022, 6, 1).unwrap(), -1.48).unwrap();
+        let [east, north, up] = super::sun_vector(noon, 53.38, -1.48);
+        assert!(east.abs() < 0.05 && north < 0.0 && up > 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn time_above_thresholds_matches_daylight_above() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let thresholds = [-90.0, 0.0, 10.0, 20.0, 30.0, 70.0];
+        let durations = super::time_above_thresholds(date, 53.38, -1.48, &thresholds);
+        assert_eq!(durations.len(), thresholds.len());
+        assert_eq!(durations[0], Duration::days(1));
+        for (&threshold, &duration) in thresholds[1..5].iter().zip(&durations[1..5]) {
+            let (start, end) = super::daylight_above(date, 53.38, -1.48, threshold).unwrap();
+            assert!((duration - (end - start)).num_seconds().abs() <= 1);
+        }
+        assert!(durations
+            .windows(2)
+            .all(|pair| pair[0] > pair[1] || pair[1].is_zero()));
+        //the sun gets no higher than ~59° at this latitude on this date
+        assert_eq!(durations[5], Duration::zero());
+    }
+
+    #[test]
+    fn daylight_transitions_along_a_track() {
+        use chrono::{TimeZone, Utc};
+
+        //staying put, the transitions are the sunrise and sunset
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let start = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        let track = (0..24 * 12).map(|step| (start + Duration::minutes(5 * step), 53.38, -1.48));
+        let transitions: Vec<_> = super::daylight_transitions(track).collect();
+        let (rise, set) = super::sun_times(date, 53.38, -1.48, 0.0).unwrap();
+        assert_eq!(transitions.len(), 2);
+        assert!(transitions[0].is_sunrise && !transitions[1].is_sunrise);
+        //the altitude and the sunrise equation agree to within a few minutes
+        assert!((transitions[0].time - rise).num_minutes().abs() <= 5);
+        assert!((transitions[1].time - set).num_minutes().abs() <= 5);
+        assert_eq!(transitions[0].latitude, 53.38);
+        assert!((transitions[0].longitude - -1.48).abs() < 1e-9);
+
+        //flying east across the antimeridian into the dawn
+        let start = Utc.with_ymd_and_hms(2022, 6, 1, 17, 0, 0).unwrap();
+        let track = (0..=60).map(|minute| {
+            let longitude = 170.0 + f64::from(minute) / 3.0;
+            let longitude = if longitude > 180.0 {
+                longitude - 360.0
+            } else {
+                longitude
+            };
+            (start + Duration::minutes(minute.into()), 0.0, longitude)
+        });
+        let transitions: Vec<_> = super::daylight_transitions(track).collect();
+        assert_eq!(transitions.len(), 1);
+        let sunrise = transitions[0];
+        assert!(sunrise.is_sunrise);
+        assert!((-180.0..180.0).contains(&sunrise.longitude));
+        assert!(super::is_daytime(
+            sunrise.time + Duration::minutes(5),
+            0.0,
+            sunrise.longitude,
+            0.0
+        ));
+        assert!(!super::is_daytime(
+            sunrise.time - Duration::minutes(5),
+            0.0,
+            sunrise.longitude,
+            0.0
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn altitude_grid_matches_altitude() {
+        let grid = super::altitude_grid(2024, 53.38, -1.48, 48);
+        assert_eq!(grid.len(), 366);
+        for (day, row) in grid.iter().enumerate().step_by(15) {
+            assert_eq!(row.len(), 48);
+            let date = NaiveDate::from_yo_opt(2024, day as u32 + 1).unwrap();
+            for (sample, altitude) in row.iter().enumerate() {
+                let date_time = date.and_hms_opt(0, 0, 0).unwrap().and_utc()
+                    + Duration::minutes(30 * sample as i64);
+                assert!((altitude - super::altitude(date_time, 53.38, -1.48)).abs() < 0.002);
+            }
+        }
+        assert!(super::altitude_grid(i32::MAX, 53.38, -1.48, 24).is_empty());
+    }
+
+    #[test]
+    fn daylight_above_gives_prayer_times() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (rise, set) = super::sun_times(date, 21.42, 39.83, 0.0).unwrap();
+        let (fajr, isha) = super::daylight_above(date, 21.42, 39.83, -18.0).unwrap();
+        assert!(fajr < rise && set < isha);
+        for time in [fajr, isha] {
+            assert!((super::altitude(time, 21.42, 39.83) + 18.0).abs() < 0.5);
+        }
+
+        //at Asr the shadow is the object's height longer than it was at noon
+        let noon = super::solar_noon(date, 39.83).unwrap();
+        let noon_shadow = super::shadow_length(noon, 21.42, 39.83, 1.0).unwrap();
+        let asr_altitude = super::shadow_ratio_altitude(date, 21.42, 1.0);
+        let (_, asr) = super::daylight_above(date, 21.42, 39.83, asr_altitude).unwrap();
+        assert!(noon < asr && asr < set);
+        let asr_shadow = super::shadow_length(asr, 21.42, 39.83, 1.0).unwrap();
+        assert!((asr_shadow - noon_shadow - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn julian_calendar_dates_convert_to_gregorian() {
+        use super::{from_julian_calendar, to_julian_calendar};
+
+        for ((year, month, day), gregorian) in [
+            //the switch to the Gregorian calendar, the Julian 4th of October being followed by the Gregorian 15th
+            ((1582, 10, 4), (1582, 10, 14)),
+            ((1582, 10, 5), (1582, 10, 15)),
+            //a leap day only in the Julian calendar
+            ((1500, 2, 29), (1500, 3, 10)),
+            ((1900, 2, 29), (1900, 3, 13)),
+            //in 1BC, and in the 3rd century, the two calendars agree
+            ((0, 1, 1), (-1, 12, 30)),
+            ((250, 6, 1), (250, 6, 1)),
+        ] {
+            let gregorian = NaiveDate::from_ymd_opt(gregorian.0, gregorian.1, gregorian.2).unwrap();
+            assert_eq!(from_julian_calendar(year, month, day), Some(gregorian));
+            assert_eq!(to_julian_calendar(gregorian), (year, month, day));
+        }
+
+        assert_eq!(from_julian_calendar(1501, 2, 29), None);
+        assert_eq!(from_julian_calendar(1500, 13, 1), None);
+        assert_eq!(from_julian_calendar(1500, 4, 31), None);
+        assert_eq!(from_julian_calendar(i32::MAX, 1, 1), None);
+
+        let mut date = NaiveDate::from_ymd_opt(-1000, 1, 1).unwrap();
+        while date < NaiveDate::from_ymd_opt(2100, 1, 1).unwrap() {
+            let (year, month, day) = to_julian_calendar(date);
+            assert_eq!(from_julian_calendar(year, month, day), Some(date));
+            date += Duration::days(97);
+        }
+    }
+
+    #[test]
+    fn sun_is_overhead_at_the_subsolar_point() {
+        use chrono::{TimeZone, Utc};
+
+        let solstice = Utc.with_ymd_and_hms(2022, 6, 21, 12, 0, 0).unwrap();
+        let (latitude, longitude) = super::subsolar_point(solstice);
+        assert!((latitude - 23.44).abs() < 0.05);
+        //solar noon at Greenwich is ~2 minutes after 12:00 UTC on the solstice, so the sun is just east of it
+        assert!(longitude > 0.0 && longitude < 1.0);
+
+        for hour in (0..24 * 365).step_by(97) {
+            let date_time =
+                Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap() + Duration::hours(hour);
+            let (latitude, longitude) = super::subsolar_point(date_time);
+            assert!((-180.0..180.0).contains(&longitude));
+            assert!(super::altitude(date_time, latitude, longitude) > 89.9);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn sun_is_on_the_horizon_along_the_terminator() {
+        use chrono::{TimeZone, Utc};
+
+        for date_time in [
+            Utc.with_ymd_and_hms(2022, 6, 21, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2022, 3, 20, 15, 33, 0).unwrap(),
+            Utc.with_ymd_and_hms(2022, 12, 21, 3, 0, 0).unwrap(),
+        ] {
+            let points = super::terminator(date_time, 72);
+            assert_eq!(points.len(), 72);
+            let (subsolar_latitude, _) = super::subsolar_point(date_time);
+            assert!((points[0].0 - (90.0 - subsolar_latitude.abs())).abs() < 1e-6);
+            for (latitude, longitude) in points {
+                assert!((-180.0..180.0).contains(&longitude));
+                assert!(super::altitude(date_time, latitude, longitude).abs() < 0.01);
+            }
+        }
+        assert!(super::terminator(Utc::now(), 0).is_empty());
+    }
+
+    #[test]
+    fn sun_window_spans_the_solstice_azimuths() {
+        let window = super::annual_sun_window(53.38);
+        for (month, day) in [(6, 21), (12, 21)] {
+            let date = NaiveDate::from_ymd_opt(2022, month, day).unwrap();
+            let rise = super::sunrise_azimuth(date, 53.38, -1.48, 0.0).unwrap();
+            let set = super::sunset_azimuth(date, 53.38, -1.48, 0.0).unwrap();
+            let (northernmost, southernmost) = window.sunrise_azimuth_range;
+            assert!(
+                (rise - northernmost).abs() < 0.5 || (rise - southernmost).abs() < 0.5,
+                "{rise}"
+            );
+            let (southernmost, northernmost) = window.sunset_azimuth_range;
+            assert!((set - northernmost).abs() < 0.5 || (set - southernmost).abs() < 0.5);
+
+            let noon = super::max_altitude(date, 53.38);
+            assert!(
+                (noon - window.max_noon_altitude).abs() < 0.05
+                    || (noon - window.min_noon_altitude).abs() < 0.05
+            );
+        }
+        assert!(window.min_noon_altitude < window.max_noon_altitude);
+
+        //in the southern hemisphere the summer solstice is in december
+        let southern = super::annual_sun_window(-53.38);
+        assert_eq!(southern.max_noon_altitude, window.max_noon_altitude);
+        //and the sunrise azimuths are mirrored north to south
+        let (northernmost, southernmost) = southern.sunrise_azimuth_range;
+        assert!((northernmost - (180.0 - window.sunrise_azimuth_range.1)).abs() < 1e-9);
+        assert!((southernmost - (180.0 - window.sunrise_azimuth_range.0)).abs() < 1e-9);
+
+        //within the arctic circle the sun doesn't rise on the winter solstice, or set on the summer one
+        let arctic = super::annual_sun_window(80.0);
+        assert_eq!(arctic.sunrise_azimuth_range, (0.0, 180.0));
+        assert!(arctic.min_noon_altitude < 0.0);
+
+        //within the tropics the sun is overhead at noon twice a year, rather than highest on the solstice
+        for latitude in [0.0, 10.0, -20.0] {
+            let tropical = super::annual_sun_window(latitude);
+            assert_eq!(tropical.max_noon_altitude, 90.0);
+            let noon_altitudes: Vec<_> = NaiveDate::from_ymd_opt(2022, 1, 1)
+                .unwrap()
+                .iter_days()
+                .take(365)
+                .map(|date| super::max_altitude(date, latitude))
+                .collect();
+            let highest = noon_altitudes.iter().copied().fold(f64::MIN, f64::max);
+            let lowest = noon_altitudes.iter().copied().fold(f64::MAX, f64::min);
+            assert!(highest > 89.5);
+            assert!((lowest - tropical.min_noon_altitude).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn solar_events_match_the_individual_calculations() {
+        use super::Twilight;
+
+        //including at 70°N, which has midnight sun for part of the month, and the antimeridian
+        for (latitude, longitude) in [
+            (53.38, -1.48),
+            (70.0, 20.0),
+            (-36.85, 174.76),
+            (51.0, 179.9),
+        ] {
+            for day in (1..=30).step_by(3) {
+                let date = NaiveDate::from_ymd_opt(2022, 5, day).unwrap();
+                let events = super::solar_events(date, latitude, longitude, 100.0);
+                let twilight = |kind| super::twilight_times(date, latitude, longitude, 100.0, kind);
+                let sun_times = super::sun_times(date, latitude, longitude, 100.0);
+                let golden_hour = super::golden_hour(date, latitude, longitude, 100.0);
+
+                assert_eq!(events.sunrise, sun_times.map(|(rise, _)| rise));
+                assert_eq!(events.sunset, sun_times.map(|(_, set)| set));
+                assert_eq!(events.solar_noon, super::solar_noon(date, longitude));
+                assert_eq!(
+                    (events.civil_dawn, events.civil_dusk),
+                    twilight(Twilight::Civil).unzip()
+                );
+                assert_eq!(
+                    (events.nautical_dawn, events.nautical_dusk),
+                    twilight(Twilight::Nautical).unzip()
+                );
+                assert_eq!(
+                    (events.astronomical_dawn, events.astronomical_dusk),
+                    twilight(Twilight::Astronomical).unzip()
+                );
+                assert_eq!(
+                    (events.morning_golden_hour, events.evening_golden_hour),
+                    golden_hour
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sun_times_difference_is_later_minus_earlier() {
+        use super::SunTimes;
+
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let sheffield = SunTimes::from(super::sun_times(date, 53.38, -1.48, 0.0).unwrap());
+        //15° further west the sun rises and sets an hour later
+        let west = SunTimes::from(super::sun_times(date, 53.38, -16.48, 0.0).unwrap());
+        let (sunrise, sunset) = west.difference(&sheffield);
+        assert!((sunrise.num_minutes() - 60).abs() <= 1);
+        assert!((sunset.num_minutes() - 60).abs() <= 1);
+        assert_eq!(sheffield.difference(&west), (-sunrise, -sunset));
+        assert_eq!(
+            sheffield.difference(&sheffield),
+            (Duration::zero(), Duration::zero())
+        );
+    }
+
+    #[test]
+    fn days_and_nights_are_equal_at_the_equinoxes() {
+        use chrono::{TimeZone, Utc};
+
+        let mut after = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        for _ in 0..2 * 50 {
+            let equinox = super::next_equinox(after).unwrap();
+            after = equinox + Duration::days(1);
+            //calculate the solar day centred on the equinox, where the sun is overhead then, so that the sun's own
+            //motion through the day doesn't count against it
+            let (_, longitude) = super::subsolar_point(equinox);
+            let equation =
+                super::generic::SunriseEquation::new(super::JulianDate::from(equinox), longitude);
+            let noon_offset = (equation.solar_transit() - super::JulianDate::from(equinox)).days();
+            let equation = if noon_offset > 0.5 {
+                super::generic::SunriseEquation::new(
+                    super::JulianDate::from(equinox) - super::JulianDays(1.0),
+                    longitude,
+                )
+            } else if noon_offset < -0.5 {
+                super::generic::SunriseEquation::new(
+                    super::JulianDate::from(equinox) + super::JulianDays(1.0),
+                    longitude,
+                )
+            } else {
+                equation
+            };
+
+            let day_length = |latitude: f64| match equation.crossing(latitude, 0.0, 0.0) {
+                Some(super::generic::Crossing::At(rise, set)) => {
+                    (rise, set, (set - rise).days() * 24.0 * 60.0)
+                }
+                _ => panic!("the sun should rise and set everywhere on the equinox"),
+            };
+            for latitude in [0.0, 15.0, 30.0, 45.0, 60.0] {
+                let (rise, set, north) = day_length(latitude);
+                let (_, _, south) = day_length(-latitude);
+                //the sun's centre is up for 12 hours, or within half a minute of it, in both hemispheres
+                assert!(
+                    (north - 12.0 * 60.0).abs() < 0.5,
+                    "{equinox} {latitude}: {north}"
+                );
+                assert!(
+                    (south - 12.0 * 60.0).abs() < 0.5,
+                    "{equinox} {latitude}: {south}"
+                );
+                //and it rises due east and sets due west
+                let azimuth = |time: super::JulianDate| {
+                    super::generic::position(time, latitude, longitude).azimuth
+                };
+                assert!((azimuth(rise) - 90.0).abs() < 0.5, "{equinox} {latitude}");
+                assert!((azimuth(set) - 270.0).abs() < 0.5, "{equinox} {latitude}");
+            }
+        }
+    }
+
+    #[test]
+    fn sun_is_largest_at_perihelion() {
+        let diameters = (1..=365)
+            .map(|ordinal| {
+                let date = NaiveDate::from_yo_opt(2022, ordinal).unwrap();
+                (date, super::angular_diameter(date))
+            })
+            .collect::<Vec<_>>();
+        let (largest_date, largest) = diameters
+            .iter()
+            .copied()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        let (smallest_date, smallest) = diameters
+            .iter()
+            .copied()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+
+        //perihelion and aphelion were on the 4th of january and july in 2022
+        assert!(
+            (largest_date - NaiveDate::from_ymd_opt(2022, 1, 4).unwrap())
+                .num_days()
+                .abs()
+                <= 2
+        );
+        assert!(
+            (smallest_date - NaiveDate::from_ymd_opt(2022, 7, 4).unwrap())
+                .num_days()
+                .abs()
+                <= 2
+        );
+        //the sun's angular diameter ranges from 31.6' to 32.5'
+        assert!((largest * 60.0 - 32.53).abs() < 0.05);
+        assert!((smallest * 60.0 - 31.47).abs() < 0.05);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn sun_times_grid_matches_sun_times() {
+        let points: Vec<_> = (-8..=8)
+            .flat_map(|latitude| {
+                (-12..=12).map(move |longitude| {
+                    (
+                        latitude as f64 * 10.0,
+                        longitude as f64 * 15.0 - 0.01,
+                        100.0,
+                    )
+                })
+            })
+            .collect();
+        for (month, day) in [(3, 20), (6, 21), (9, 1), (12, 31)] {
+            let date = NaiveDate::from_ymd_opt(2022, month, day).unwrap();
+            let grid = super::sun_times_grid(date, &points);
+            assert_eq!(grid.len(), points.len());
+            for (&(latitude, longitude, elevation), times) in points.iter().zip(grid) {
+                match (
+                    times,
+                    super::sun_times(date, latitude, longitude, elevation),
+                ) {
+                    (Some((rise, set)), Some((expected_rise, expected_set))) => {
+                        //where the sun only just rises small differences in the declination matter more
+                        let tolerance = if latitude.abs() > 70.0 { 5 } else { 1 };
+                        assert!((rise - expected_rise).num_seconds().abs() <= tolerance);
+                        assert!((set - expected_set).num_seconds().abs() <= tolerance);
+                    }
+                    (None, None) => {}
+                    (times, expected) => {
+                        panic!("{latitude}, {longitude}: {times:?} rather than {expected:?}")
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn max_altitude_time_follows_the_declination() {
+        //around the solstices the declination is still, so the sun is highest as it crosses the meridian
+        let solstice = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        let noon = super::solar_noon(solstice, -1.48).unwrap();
+        let highest = super::max_altitude_time(solstice, 53.38, -1.48).unwrap();
+        assert!((highest - noon).num_seconds().abs() <= 1);
+
+        //while in March the sun is moving north, so is still climbing in the north (and already sinking in the south)
+        //as it crosses the meridian
+        let equinox = NaiveDate::from_ymd_opt(2022, 3, 20).unwrap();
+        let noon = super::solar_noon(equinox, 18.96).unwrap();
+        let north = super::max_altitude_time(equinox, 69.65, 18.96).unwrap();
+        let south = super::max_altitude_time(equinox, -69.65, 18.96).unwrap();
+        assert!((north - noon).num_seconds() > 30);
+        assert!((noon - south).num_seconds() > 30);
+        for minutes in [-1, 1] {
+            let near = north + Duration::minutes(minutes);
+            assert!(super::altitude(north, 69.65, 18.96) > super::altitude(near, 69.65, 18.96));
+        }
+        assert!(super::altitude(north, 69.65, 18.96) > super::altitude(noon, 69.65, 18.96));
+
+        //at the pole the sun only climbs with the declination
+        assert_eq!(super::max_altitude_time(equinox, 90.0, 0.0), None);
+    }
+
+    #[test]
+    fn sun_times_debug_exposes_the_calculation() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let values = super::sun_times_debug(date, 53.38, -1.48, 100.0).unwrap();
+        let (rise, set) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+        assert_eq!(values.sunrise, Some(rise));
+        assert_eq!(values.sunset, Some(set));
+        assert_eq!(values.julian_date, super::JulianDate::from_date(date));
+        assert_eq!(values.days_since_2000, 8187.0);
+        assert!((values.mean_solar_time - (8187.0 + 1.48 / 360.0)).abs() < 1e-9);
+        assert_eq!(
+            values.solar_transit.to_datetime(),
+            super::solar_noon(date, -1.48)
+        );
+        let detailed = super::sun_times_detailed(date, 53.38, -1.48, 100.0).unwrap();
+        assert!((values.event_hour_angle.unwrap() - detailed.hour_angle).abs() < 1e-9);
+
+        //the values along the sun's orbit are those at mean solar noon, so match the functions for them at greenwich
+        let greenwich = super::sun_times_debug(date, 53.38, 0.0, 0.0).unwrap();
+        assert_eq!(
+            greenwich.solar_mean_anomaly,
+            super::solar_mean_anomaly(date)
+        );
+        assert_eq!(
+            greenwich.ecliptic_longitude,
+            super::ecliptic_longitude(date)
+        );
+        assert_eq!(greenwich.declination, super::declination(date));
+
+        //the intermediate values are still there when the sun doesn't set
+        let values = super::sun_times_debug(date, 80.0, 0.0, 0.0).unwrap();
+        assert_eq!(values.event_hour_angle, None);
+        assert_eq!(values.sunrise, None);
+        assert!(values.declination > 21.0);
+    }
+
+    #[test]
+    fn sun_track_steps_through_the_day() {
+        use chrono::{TimeZone, Utc};
+
+        let start = Utc.with_ymd_and_hms(2022, 6, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2022, 6, 2, 0, 0, 0).unwrap();
+        let track: Vec<_> =
+            super::sun_track(start, end, Duration::minutes(10), 53.38, -1.48).collect();
+        assert_eq!(track.len(), 145);
+        assert_eq!(track.first().unwrap().0, start);
+        assert_eq!(track.last().unwrap().0, end);
+        for &(date_time, position) in &track {
+            let params = super::DaySolarParams::for_date(date_time.date_naive());
+            assert_eq!(position, params.sun_position(date_time, 53.38, -1.48));
+            assert!((position.altitude - super::altitude(date_time, 53.38, -1.48)).abs() < 0.002);
+        }
+
+        assert_eq!(
+            super::sun_track(start, end, Duration::zero(), 53.38, -1.48).count(),
+            0
+        );
+        assert_eq!(
+            super::sun_track(end, start, Duration::minutes(10), 53.38, -1.48).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn civil_offsets_are_the_gaps_from_sunrise_and_sunset() {
+        use super::Twilight;
+
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let (sunrise, sunset) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+        let (dawn, dusk) =
+            super::twilight_times(date, 53.38, -1.48, 100.0, Twilight::Civil).unwrap();
+        assert_eq!(
+            super::civil_dawn_offset(date, 53.38, -1.48, 100.0),
+            Some(sunrise - dawn)
+        );
+        assert_eq!(
+            super::civil_dusk_offset(date, 53.38, -1.48, 100.0),
+            Some(dusk - sunset)
+        );
+        //civil twilight lasts a little over half an hour at this latitude
+        let offset = super::civil_dawn_offset(date, 53.38, -1.48, 100.0).unwrap();
+        assert!((30..45).contains(&offset.num_minutes()));
+
+        //in the summer at Tromsø it never gets 6° below the horizon
+        let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        assert_eq!(super::civil_dawn_offset(date, 69.65, 18.96, 0.0), None);
+        assert_eq!(super::civil_dusk_offset(date, 69.65, 18.96, 0.0), None);
+    }
+
+    #[test]
+    fn hour_angle_at_altitude_matches_sun_times_with_angle() {
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        for altitude in [-6.0, 0.0, 10.0, 20.0] {
+            let (rise, set) = super::sun_times_with_angle(date, 53.38, 0.0, 0.0, altitude).unwrap();
+            let hour_angle = super::hour_angle_at_altitude(date, 53.38, altitude).unwrap();
+            //15° of hour angle is an hour, so half the time between the crossings is 4 minutes a degree
+            let half_day = (set - rise).num_seconds() as f64 / 2.0;
+            assert!((hour_angle * 240.0 - half_day).abs() < 2.0);
+        }
+
+        //the sun doesn't get up to 30° in early March, and never sets at midsummer in Tromsø
+        assert_eq!(super::hour_angle_at_altitude(date, 53.38, 30.0), None);
+        let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        assert_eq!(super::hour_angle_at_altitude(date, 69.65, 0.0), None);
+        assert_eq!(super::hour_angle_at_altitude(date, 0.0, 90.0), None);
+    }
+
+    #[test]
+    fn high_elevations_still_have_sun_times() {
+        //the summit of Everest has a sunrise and sunset every day
+        let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        for day in 0..365 {
+            let date = start + chrono::Duration::days(day);
+            assert!(super::sun_times(date, 27.99, 86.92, 8848.0).is_some());
+        }
+
+        //the summit of Denali sees the sun earlier and later than sea level, just below the arctic circle in June
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let (sunrise, sunset) = super::sun_times(date, 63.07, -151.0, 6190.0).unwrap();
+        let (sea_level_sunrise, sea_level_sunset) =
+            super::sun_times(date, 63.07, -151.0, 0.0).unwrap();
+        assert!(sunrise < sea_level_sunrise - chrono::Duration::minutes(30));
+        assert!(sunset > sea_level_sunset + chrono::Duration::minutes(30));
+
+        //far above the earth the sun is always in view, rather than the horizon wrapping around past straight down
+        assert_eq!(super::sun_times(date, 63.07, -151.0, 1e8), None);
+        assert_eq!(super::sun_times(date, 0.0, 0.0, 1e8), None);
+        assert_eq!(super::sun_times_precise(date, 0.0, 0.0, 1e8), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn sun_times_grouped_matches_the_cell_centres() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let points: Vec<_> = (0..20)
+            .flat_map(|y| {
+                (0..20).map(move |x| (53.3 + y as f64 * 0.01, -1.6 + x as f64 * 0.01, 100.0))
+            })
+            .collect();
+
+        let grouped = super::sun_times_grouped(date, &points, 0.05);
+        let exact = super::sun_times_grid(date, &points);
+        assert_eq!(grouped.len(), points.len());
+        for (grouped, exact) in grouped.iter().zip(&exact) {
+            let ((rise, set), (exact_rise, exact_set)) = (grouped.unwrap(), exact.unwrap());
+            //at most 0.025° from the centre of the cell, which is a few seconds
+            assert!((rise - exact_rise).num_seconds().abs() <= 20);
+            assert!((set - exact_set).num_seconds().abs() <= 20);
+        }
+        //the 400 locations fall in 16 cells, or 25 as their edges are on the points
+        let mut distinct: Vec<_> = grouped.iter().map(|times| times.unwrap()).collect();
+        distinct.sort();
+        distinct.dedup();
+        assert!(distinct.len() <= 25);
+
+        //the cell's times are for the average elevation
+        assert_eq!(
+            super::sun_times_grouped(date, &[(53.38, -1.48, 0.0), (53.38, -1.48, 200.0)], 0.05),
+            vec![super::sun_times(date, 53.4, -1.5, 100.0); 2]
+        );
+
+        assert_eq!(super::sun_times_grouped(date, &points, 0.0), exact);
+        assert_eq!(super::sun_times_grouped(date, &[], 0.05), vec![]);
+    }
+
+    #[test]
+    fn solar_progress_goes_from_sunrise_to_sunset() {
+        use chrono::{TimeZone, Utc};
+
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        //far from greenwich as well, where the sunset on the UTC date is that of the day before
+        for (latitude, longitude) in [(53.38, -1.48), (-33.87, 151.21)] {
+            let (sunrise, _) = super::sun_times(date, latitude, longitude, 100.0).unwrap();
+            let sunset = super::next_sunset(sunrise, latitude, longitude, 100.0).unwrap();
+            let daylight = sunset - sunrise;
+            let progress = |date_time| super::solar_progress(date_time, latitude, longitude, 100.0);
+
+            assert_eq!(progress(sunrise), Some(0.0));
+            assert_eq!(progress(sunset), Some(1.0));
+            let quarter = progress(sunrise + daylight / 4).unwrap();
+            assert!((quarter - 0.25).abs() < 1e-6);
+            assert_eq!(progress(sunrise - chrono::Duration::minutes(1)), None);
+            assert_eq!(progress(sunset + chrono::Duration::minutes(1)), None);
+        }
+
+        //there's no sunrise or sunset to measure from in the polar day at Tromsø
+        let date_time = Utc.with_ymd_and_hms(2022, 6, 21, 12, 0, 0).unwrap();
+        assert_eq!(super::solar_progress(date_time, 69.65, 18.96, 0.0), None);
+    }
+
+    #[test]
+    fn leap_second_days_convert_like_any_other() {
+        use super::{JulianDate, JulianDays};
+        use chrono::{TimeZone, Utc};
+
+        //a leap second was inserted at the end of 2016, as 23:59:60 UTC
+        let date = NaiveDate::from_ymd_opt(2016, 12, 31).unwrap();
+        let end_of_2016 = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap();
+        let julian_end_of_2016 = JulianDate::from_datetime(end_of_2016);
+        assert_eq!(julian_end_of_2016.to_datetime(), Some(end_of_2016));
+        assert_eq!(
+            (julian_end_of_2016 - JulianDays(0.4 / 86400.0)).to_datetime(),
+            Some(end_of_2016)
+        );
+        assert_eq!(
+            (julian_end_of_2016 - JulianDays(0.6 / 86400.0)).to_datetime(),
+            Utc.with_ymd_and_hms(2016, 12, 31, 23, 59, 59).single()
+        );
+
+        //chrono's representation of the leap second itself is taken as the second before it
+        let leap_second =
+            Utc.from_utc_datetime(&date.and_hms_milli_opt(23, 59, 59, 1_500).unwrap());
+        assert_eq!(
+            JulianDate::from_datetime(leap_second).to_datetime(),
+            Utc.with_ymd_and_hms(2016, 12, 31, 23, 59, 59).single()
+        );
+
+        //sunrises and sunsets around midnight UTC on that day, e.g. in the pacific, are still found on it
+        for longitude in (-180..=180).step_by(5) {
+            let (sunrise, sunset) = super::sun_times(date, 0.0, longitude as f64, 0.0).unwrap();
+            assert_eq!((sunrise.date_naive(), sunset.date_naive()), (date, date));
+        }
+    }
+
+    #[test]
+    fn astronomical_night_length_is_between_dusk_and_dawn() {
+        use super::Twilight;
+
+        //near greenwich the night follows the dusk, and far from it the dusk on the UTC date is the night before's
+        let date = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        for (latitude, longitude) in [(53.38, -1.48), (-33.87, 151.21), (40.0, -170.0)] {
+            let (dawn, dusk) =
+                super::twilight_times(date, latitude, longitude, 0.0, Twilight::Astronomical)
+                    .unwrap();
+            let night = super::astronomical_night_length(date, latitude, longitude).unwrap();
+            assert!(night > Duration::zero() && night < Duration::days(1));
+            if dusk > dawn {
+                let next_dawn = super::twilight_times(
+                    date + Duration::days(1),
+                    latitude,
+                    longitude,
+                    0.0,
+                    Twilight::Astronomical,
+                )
+                .unwrap()
+                .0;
+                assert!((night - (next_dawn - dusk)).num_seconds().abs() < 120);
+            } else {
+                assert!((night - (dawn - dusk)).num_seconds().abs() < 120);
+            }
+        }
+        //over 12 hours of full darkness on the longest night at Sheffield, and under 8 on the shortest in Sydney
+        assert_eq!(
+            super::astronomical_night_length(date, 53.38, -1.48)
+                .unwrap()
+                .num_hours(),
+            12
+        );
+        assert!(
+            super::astronomical_night_length(date, -33.87, 151.21).unwrap() < Duration::hours(8)
+        );
+
+        //white nights at midsummer in Sheffield, and always dark in the polar night near the north pole
+        let date = NaiveDate::from_ymd_opt(2022, 6, 21).unwrap();
+        assert_eq!(super::astronomical_night_length(date, 53.38, -1.48), None);
+        let date = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        assert_eq!(
+            super::astronomical_night_length(date, 89.0, 0.0),
+            Some(Duration::days(1))
+        );
+    }
+
+    #[test]
+    fn radian_variants_match_degrees() {
+        use chrono::{TimeZone, Utc};
+
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        for (latitude, longitude) in [
+            (53.38f64, -1.48f64),
+            (-33.87, 151.21),
+            (0.0, -179.0),
+            (78.22, 15.65),
+        ] {
+            let (latitude_rad, longitude_rad) = (latitude.to_radians(), longitude.to_radians());
+            for hours in (0..365 * 24).step_by(97) {
+                let date_time = start + Duration::hours(hours);
+                let altitude = super::altitude_rad(date_time, latitude_rad, longitude_rad);
+                let azimuth = super::azimuth_rad(date_time, latitude_rad, longitude_rad);
+                assert!(
+                    (altitude.to_degrees() - super::altitude(date_time, latitude, longitude)).abs()
+                        < 1e-9
+                );
+                //the azimuths can be either side of north
+                let difference =
+                    azimuth.to_degrees() - super::azimuth(date_time, latitude, longitude);
+                assert!(((difference + 180.0).rem_euclid(360.0) - 180.0).abs() < 1e-9);
+                assert!((0.0..core::f64::consts::TAU).contains(&azimuth));
+
+                let date = date_time.date_naive();
+                let times = super::sun_times_rad(date, latitude_rad, longitude_rad, 100.0);
+                let expected = super::sun_times(date, latitude, longitude, 100.0);
+                assert_eq!(times.is_some(), expected.is_some());
+                if let (Some((rise, set)), Some((expected_rise, expected_set))) = (times, expected)
+                {
+                    assert!((rise - expected_rise).num_seconds().abs() <= 1);
+                    assert!((set - expected_set).num_seconds().abs() <= 1);
+                }
+            }
         }
     }
 }
@@ -0,0 +1,89 @@
+//! Sun times in the timezone of the location, looked up from its latitude and longitude
+//!
+//! The timezone boundaries are from tzf-rs, which bundles a simplified copy of
+//! <https://github.com/evansiroky/timezone-boundary-builder>, and the timezones themselves are from chrono-tz
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+use tzf_rs::DefaultFinder;
+
+use crate::sun_times_tz;
+
+/// Finds the timezone at a given latitude and longitude (both expressed as degrees)
+fn timezone_at(latitude: f64, longitude: f64) -> Option<Tz> {
+    //loading the boundaries takes a while, so only do it once
+    static FINDER: OnceLock<DefaultFinder> = OnceLock::new();
+    FINDER
+        .get_or_init(DefaultFinder::new)
+        .get_tz_name(longitude, latitude)
+        .parse()
+        .ok()
+}
+
+/// Calculates the approximate sunset and sunrise times on a given local date, at a given latitude, longitude, and altitude, in the timezone at that location
+///
+/// This is [sun_times_tz](crate::sun_times_tz), with the timezone looked up from the latitude and longitude, so
+/// daylight saving time is accounted for. Out at sea the timezone is the nautical one for the longitude, e.g.
+/// `Etc/GMT+2`. Elevation is handled in the same way as in [sun_times](crate::sun_times)
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in the local timezone
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if no timezone is known for the location, the date is not representable in chrono (~5M years from now), or the sun does not both rise and set on that date locally
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_local;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building, in British Summer Time
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_local(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+pub fn sun_times_local(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
+    let timezone = timezone_at(latitude, longitude)?;
+    sun_times_tz(date, &timezone, latitude, longitude, elevation)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Offset};
+    use chrono_tz::Tz;
+
+    #[test]
+    fn times_are_in_the_local_timezone() {
+        let summer = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let winter = NaiveDate::from_ymd_opt(2022, 12, 1).unwrap();
+        for (date, offset) in [(summer, 3600), (winter, 0)] {
+            let (rise, set) = super::sun_times_local(date, 53.38, -1.48, 100.0).unwrap();
+            assert_eq!(rise.timezone(), Tz::Europe__London);
+            assert_eq!(rise.offset().fix().local_minus_utc(), offset);
+            assert_eq!(rise.date_naive(), date);
+            assert_eq!(set.date_naive(), date);
+        }
+
+        let times = super::sun_times_local(summer, 35.68, 139.69, 40.0).unwrap();
+        assert_eq!(times.0.timezone(), Tz::Asia__Tokyo);
+        assert_eq!(
+            Some(times),
+            crate::sun_times_tz(summer, &Tz::Asia__Tokyo, 35.68, 139.69, 40.0)
+        );
+    }
+}
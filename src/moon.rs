@@ -0,0 +1,289 @@
+//! Calculations for the moon
+//!
+//! The moon moves roughly 13° a day against the stars, so unlike the sun its rise and set can't be found from a single
+//! transit per day. Instead its altitude is sampled through the day, and the horizon crossings found by fitting a
+//! parabola to each pair of hours
+
+use chrono::{DateTime, NaiveDate, Utc};
+//only used for the float methods in no_std builds, where std's inherent versions aren't available
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::generic::{greenwich_sidereal_time, obliquity_of_the_ecliptic, rem_euclid};
+use crate::{apparent_ecliptic_longitude, JulianDate, JAN_2000};
+
+/// The altitude of the moon's centre at moonrise and moonset, accounting for its parallax, semi-diameter, and
+/// atmospheric refraction
+const MOONRISE_MOONSET_ANGLE: f64 = 0.125;
+
+/// The moon's position in equatorial coordinates, both expressed as radians
+struct MoonCoordinates {
+    right_ascension: f64,
+    declination: f64,
+}
+
+/// Calculates the moon's approximate ecliptic longitude and latitude at a given moment, both expressed as radians
+fn moon_ecliptic_coordinates(julian_date: JulianDate) -> (f64, f64) {
+    //see https://aa.quae.nl/en/reken/hemelpositie.html#4
+    let days_since_2000 = (julian_date - JAN_2000).0;
+
+    let mean_longitude = 218.316 + 13.176396 * days_since_2000;
+    let mean_anomaly = (134.963 + 13.064993 * days_since_2000).to_radians();
+    let mean_distance = (93.272 + 13.229350 * days_since_2000).to_radians();
+
+    (
+        (mean_longitude + 6.289 * mean_anomaly.sin()).to_radians(),
+        (5.128 * mean_distance.sin()).to_radians(),
+    )
+}
+
+/// Calculates the moon's approximate equatorial coordinates at a given moment
+fn moon_coordinates(julian_date: JulianDate) -> MoonCoordinates {
+    let (ecliptic_longitude, ecliptic_latitude) = moon_ecliptic_coordinates(julian_date);
+
+    let obliquity = obliquity_of_the_ecliptic((julian_date - JAN_2000).0).to_radians();
+    let right_ascension = (ecliptic_longitude.sin() * obliquity.cos()
+        - ecliptic_latitude.tan() * obliquity.sin())
+    .atan2(ecliptic_longitude.cos());
+    let declination = (ecliptic_latitude.sin() * obliquity.cos()
+        + ecliptic_latitude.cos() * obliquity.sin() * ecliptic_longitude.sin())
+    .asin();
+
+    MoonCoordinates {
+        right_ascension,
+        declination,
+    }
+}
+
+/// Calculates the altitude of the moon's centre (in degrees) at a given place and moment, ignoring parallax
+fn moon_altitude(julian_date: JulianDate, latitude: f64, longitude: f64) -> f64 {
+    let MoonCoordinates {
+        right_ascension,
+        declination,
+    } = moon_coordinates(julian_date);
+    let latitude = latitude.to_radians();
+    let local_hour_angle =
+        (greenwich_sidereal_time(julian_date) + longitude).to_radians() - right_ascension;
+
+    ((latitude.sin() * declination.sin())
+        + (latitude.cos() * declination.cos() * local_hour_angle.cos()))
+    .asin()
+    .to_degrees()
+}
+
+/// Calculates the approximate moonrise and moonset times at a given latitude and longitude
+///
+/// The moon's position is from a simplified model, which leaves out all but the largest of the perturbations to its
+/// orbit, so is only accurate to a degree or so. As the moon takes ~4 minutes to move a degree across the sky, the
+/// times can be several minutes from those in published almanacs, and more at high latitudes
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the moonrise and moonset, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the moon doesn't both rise and set on the given date. As the moon rises ~50 minutes later each day,
+///    this happens a couple of times a month even away from the poles
+///  - `Some((moonrise, moonset))` otherwise. Note that the moonset may be before the moonrise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::moon_times;
+///
+/// //Calculate the moonrise and moonset times at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// if let Some((moonrise, moonset)) = moon_times(date, 53.38, -1.48) {
+///     println!("Moonrise: {}, Moonset: {}", moonrise, moonset);
+/// }
+/// ```
+pub fn moon_times(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    //see https://github.com/mourner/suncalc, which uses the same approach
+    let start_of_day = JulianDate::from_date(date);
+    let height_at = |hours: f64| {
+        moon_altitude(
+            JulianDate(start_of_day.0 + hours / 24.0),
+            latitude,
+            longitude,
+        ) - MOONRISE_MOONSET_ANGLE
+    };
+
+    let mut rise = None;
+    let mut set = None;
+    let mut previous_height = height_at(0.0);
+    for hour in (1..24).step_by(2) {
+        let hour = f64::from(hour);
+        let height = height_at(hour);
+        let next_height = height_at(hour + 1.0);
+
+        //fit a parabola through the three heights, with x in [-1, 1] over the two hours
+        let a = (previous_height + next_height) / 2.0 - height;
+        let b = (next_height - previous_height) / 2.0;
+        let vertex_x = -b / (2.0 * a);
+        let vertex_height = (a * vertex_x + b) * vertex_x + height;
+        let discriminant = b * b - 4.0 * a * height;
+
+        if discriminant >= 0.0 {
+            let offset = discriminant.sqrt() / (a.abs() * 2.0);
+            let first_root = vertex_x - offset;
+            let second_root = vertex_x + offset;
+            match (first_root.abs() <= 1.0, second_root.abs() <= 1.0) {
+                (true, true) => {
+                    let (rise_root, set_root) = if vertex_height < 0.0 {
+                        (second_root, first_root)
+                    } else {
+                        (first_root, second_root)
+                    };
+                    rise = Some(hour + rise_root);
+                    set = Some(hour + set_root);
+                }
+                (true, false) | (false, true) => {
+                    let root = if first_root.abs() <= 1.0 {
+                        first_root
+                    } else {
+                        second_root
+                    };
+                    if previous_height < 0.0 {
+                        rise = Some(hour + root);
+                    } else {
+                        set = Some(hour + root);
+                    }
+                }
+                (false, false) => {}
+            }
+        }
+
+        if rise.is_some() && set.is_some() {
+            break;
+        }
+        previous_height = next_height;
+    }
+
+    let to_datetime = |hours: f64| JulianDate(start_of_day.0 + hours / 24.0).to_datetime();
+    Some((to_datetime(rise?)?, to_datetime(set?)?))
+}
+
+/// The name of a phase of the moon, each of which spans an eighth of the lunar month
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhaseName {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+/// The phase of the moon at a given moment
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoonPhase {
+    /// The fraction of the moon's disc which is lit, from 0 (new moon) to 1 (full moon)
+    pub illuminated_fraction: f64,
+    pub name: PhaseName,
+}
+
+/// Calculates the approximate phase of the moon on a given date
+///
+/// The phase is calculated at midday UTC. Each of the named phases covers the 45° of elongation centred on it, e.g.
+/// [PhaseName::Full] is when the moon is between 157.5° and 202.5° away from the sun, so lasts ~3.7 days
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the phase of the moon, in UTC
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::{moon_phase, PhaseName};
+///
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 14).unwrap();
+/// let phase = moon_phase(date);
+/// assert_eq!(phase.name, PhaseName::Full);
+/// println!("The moon is {:.0}% lit", phase.illuminated_fraction * 100.0);
+/// ```
+pub fn moon_phase(date: NaiveDate) -> MoonPhase {
+    const PHASES: [PhaseName; 8] = [
+        PhaseName::New,
+        PhaseName::WaxingCrescent,
+        PhaseName::FirstQuarter,
+        PhaseName::WaxingGibbous,
+        PhaseName::Full,
+        PhaseName::WaningGibbous,
+        PhaseName::LastQuarter,
+        PhaseName::WaningCrescent,
+    ];
+
+    let midday = JulianDate(JulianDate::from_date(date).0 + 0.5);
+    //how far the moon is ahead of the sun along the ecliptic, which grows from 0° at new moon to 360° at the next
+    let elongation = rem_euclid(
+        moon_ecliptic_coordinates(midday).0.to_degrees() - apparent_ecliptic_longitude(midday),
+        360.0,
+    );
+
+    MoonPhase {
+        illuminated_fraction: (1.0 - elongation.to_radians().cos()) / 2.0,
+        name: PHASES[((elongation + 22.5) / 45.0) as usize % PHASES.len()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::JulianDate;
+
+    #[test]
+    fn moon_is_on_the_horizon_at_moonrise_and_moonset() {
+        let mut days_with_both = 0;
+        for day in 1..=30 {
+            let date = NaiveDate::from_ymd_opt(2022, 6, day).unwrap();
+            if let Some((rise, set)) = super::moon_times(date, 53.38, -1.48) {
+                days_with_both += 1;
+                assert_eq!(rise.date_naive(), date);
+                assert_eq!(set.date_naive(), date);
+                for event in [rise, set] {
+                    let altitude = super::moon_altitude(JulianDate::from(event), 53.38, -1.48);
+                    assert!((altitude - super::MOONRISE_MOONSET_ANGLE).abs() < 0.05);
+                }
+            }
+        }
+        //the moon misses either its rise or its set on a couple of days a month
+        assert!(days_with_both >= 26);
+    }
+
+    #[test]
+    fn moon_phases_match_published_dates() {
+        use super::{moon_phase, PhaseName};
+
+        //from https://aa.usno.navy.mil/data/MoonPhases
+        for (month, day, name) in [
+            (6, 7, PhaseName::FirstQuarter),
+            (6, 14, PhaseName::Full),
+            (6, 21, PhaseName::LastQuarter),
+            (6, 29, PhaseName::New),
+        ] {
+            let phase = moon_phase(NaiveDate::from_ymd_opt(2022, month, day).unwrap());
+            assert_eq!(phase.name, name);
+            let expected_fraction = match name {
+                PhaseName::New => 0.0,
+                PhaseName::Full => 1.0,
+                _ => 0.5,
+            };
+            assert!((phase.illuminated_fraction - expected_fraction).abs() < 0.05);
+        }
+    }
+}
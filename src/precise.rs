@@ -0,0 +1,215 @@
+//! A more precise version of the sunrise and sunset calculation, following NOAA's solar calculator
+//!
+//! See <https://gml.noaa.gov/grad/solcalc/calcdetails.html>, which is in turn based on Jean Meeus' Astronomical
+//! Algorithms. Compared to the simplified sunrise equation used elsewhere in the crate, this adds the time dependence
+//! of the orbital elements, the eccentricity of the earth's orbit, nutation, and the change in the sun's position over
+//! the course of the day
+
+use chrono::{DateTime, NaiveDate, Utc};
+//only used for the float methods in no_std builds, where std's inherent versions aren't available
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::generic::{self, Crossing};
+use crate::{JulianDate, JAN_2000};
+
+/// The angle of the sun's centre from the zenith at sunrise/sunset, as used by NOAA
+const SUNRISE_SUNSET_ZENITH: f64 = 90.833;
+const MINUTES_PER_DAY: f64 = 24.0 * 60.0;
+
+/// The sun's declination (in degrees) and the equation of time (in minutes) at a given moment
+struct SolarCoordinates {
+    declination: f64,
+    equation_of_time: f64,
+}
+
+fn solar_coordinates(julian_date: JulianDate) -> SolarCoordinates {
+    let julian_century = (julian_date - JAN_2000).0 / 36525.0;
+
+    let mean_longitude =
+        (280.46646 + julian_century * (36000.76983 + julian_century * 0.0003032)) % 360.0;
+    let mean_anomaly = 357.52911 + julian_century * (35999.05029 - 0.0001537 * julian_century);
+    let eccentricity = 0.016708634 - julian_century * (0.000042037 + 0.0000001267 * julian_century);
+
+    let mean_anomaly_radians = mean_anomaly.to_radians();
+    let center = mean_anomaly_radians.sin()
+        * (1.914602 - julian_century * (0.004817 + 0.000014 * julian_century))
+        + (2.0 * mean_anomaly_radians).sin() * (0.019993 - 0.000101 * julian_century)
+        + (3.0 * mean_anomaly_radians).sin() * 0.000289;
+
+    //the longitude of the moon's ascending node, which drives nutation
+    let ascending_node = (125.04 - 1934.136 * julian_century).to_radians();
+    let apparent_longitude = mean_longitude + center - 0.00569 - 0.00478 * ascending_node.sin();
+
+    let mean_obliquity = 23.0
+        + (26.0
+            + (21.448
+                - julian_century
+                    * (46.815 + julian_century * (0.00059 - julian_century * 0.001813)))
+                / 60.0)
+            / 60.0;
+    let obliquity = (mean_obliquity + 0.00256 * ascending_node.cos()).to_radians();
+
+    let declination = (obliquity.sin() * apparent_longitude.to_radians().sin())
+        .asin()
+        .to_degrees();
+
+    let y = (obliquity / 2.0).tan().powi(2);
+    let mean_longitude = mean_longitude.to_radians();
+    let equation_of_time = y * (2.0 * mean_longitude).sin()
+        - 2.0 * eccentricity * mean_anomaly_radians.sin()
+        + 4.0 * eccentricity * y * mean_anomaly_radians.sin() * (2.0 * mean_longitude).cos()
+        - 0.5 * y * y * (4.0 * mean_longitude).sin()
+        - 1.25 * eccentricity * eccentricity * (2.0 * mean_anomaly_radians).sin();
+
+    SolarCoordinates {
+        declination,
+        equation_of_time: equation_of_time.to_degrees() * 4.0,
+    }
+}
+
+fn cos_event_hour_angle(latitude: f64, declination: f64, zenith: f64) -> f64 {
+    let latitude = latitude.to_radians();
+    let declination = declination.to_radians();
+    zenith.to_radians().cos() / (latitude.cos() * declination.cos())
+        - latitude.tan() * declination.tan()
+}
+
+/// Calculates the time of sunrise (or sunset, if `direction` is negative) on the day starting at `start_of_day`, using
+/// the sun's position at `julian_date`
+fn event_time(
+    julian_date: JulianDate,
+    start_of_day: JulianDate,
+    latitude: f64,
+    longitude: f64,
+    zenith: f64,
+    direction: f64,
+) -> Option<JulianDate> {
+    let SolarCoordinates {
+        declination,
+        equation_of_time,
+    } = solar_coordinates(julian_date);
+    let event_hour_angle = cos_event_hour_angle(latitude, declination, zenith)
+        .acos()
+        .to_degrees();
+    if event_hour_angle.is_nan() {
+        return None;
+    }
+    let minutes = 720.0 - 4.0 * (longitude + direction * event_hour_angle) - equation_of_time;
+    Some(JulianDate(start_of_day.0 + minutes / MINUTES_PER_DAY))
+}
+
+fn crossing(
+    julian_day: JulianDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<Crossing> {
+    let start_of_day = julian_day.start_of_day();
+    //correct for the dip of the horizon in the same way as the simplified calculation
+    let zenith = (SUNRISE_SUNSET_ZENITH + 2.076 * elevation.max(0.0).sqrt() / 60.0).min(180.0);
+
+    let declination = solar_coordinates(start_of_day).declination;
+    let cos_event_hour_angle = cos_event_hour_angle(latitude, declination, zenith);
+    if cos_event_hour_angle < -1.0 {
+        return Some(Crossing::AlwaysAbove);
+    } else if cos_event_hour_angle > 1.0 {
+        return Some(Crossing::AlwaysBelow);
+    }
+
+    //as in NOAA's calculator, refine the first estimate by recalculating the sun's position at that time
+    let event = |direction| {
+        let estimate = event_time(
+            start_of_day,
+            start_of_day,
+            latitude,
+            longitude,
+            zenith,
+            direction,
+        )?;
+        event_time(
+            estimate,
+            start_of_day,
+            latitude,
+            longitude,
+            zenith,
+            direction,
+        )
+        .or(Some(estimate))
+    };
+    Some(Crossing::At(event(1.0)?, event(-1.0)?))
+}
+
+/// Calculates the sunset and sunrise times at a given latitude, longitude, and altitude, more precisely than [sun_times](crate::sun_times)
+///
+/// This follows NOAA's solar calculator, which agrees with observations to within a minute for latitudes between
+/// ±72° (beyond which refraction is too variable to do better). The simplified calculation used by
+/// [sun_times](crate::sun_times) can be a couple of minutes out, especially around the equinoxes. In every other
+/// respect this behaves the same as [sun_times](crate::sun_times)
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in chrono (~5M years from now), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use sun_times::sun_times_precise;
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building
+/// let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+/// let (sunrise, sunset) = sun_times_precise(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+pub fn sun_times_precise(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    match generic::pick_within_day(JulianDate::from_date(date), |day| {
+        crossing(day, latitude, longitude, elevation)
+    })? {
+        Crossing::At(rise, set) => Some((rise.to_datetime()?, set.to_datetime()?)),
+        Crossing::AlwaysAbove | Crossing::AlwaysBelow => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    #[test]
+    fn matches_noaa_solar_calculator() {
+        //sunrise and sunset (as Unix timestamps) from NOAA's solar calculator formulae, for dates and places where
+        //both land on the UTC date
+        let expected = [
+            (53.38, -1.48, (2022, 3, 20), 1647756521, 1647800347),
+            (53.38, -1.48, (2022, 6, 21), 1655782640, 1655843887),
+            (53.38, -1.48, (2022, 9, 22), 1663825890, 1663869886),
+            (53.38, -1.48, (2022, 12, 21), 1671610754, 1671637725),
+            (40.7128, -74.006, (2022, 3, 20), 1647773960, 1647817690),
+            (40.7128, -74.006, (2022, 9, 22), 1663843416, 1663887188),
+            (40.7128, -74.006, (2022, 12, 21), 1671624995, 1671658309),
+            (64.1466, -21.9426, (2022, 3, 20), 1647761323, 1647805400),
+            (64.1466, -21.9426, (2022, 9, 22), 1663830633, 1663874928),
+            (64.1466, -21.9426, (2022, 12, 21), 1671621740, 1671636564),
+        ];
+        for (latitude, longitude, (year, month, day), sunrise, sunset) in expected {
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+            let (rise, set) = super::sun_times_precise(date, latitude, longitude, 0.0).unwrap();
+            assert!((rise.timestamp() - sunrise).abs() <= 10);
+            assert!((set.timestamp() - sunset).abs() <= 10);
+        }
+    }
+}
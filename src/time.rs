@@ -0,0 +1,191 @@
+//! Versions of the calculations using the time crate's date and time types, rather than chrono's
+//!
+//! These are the same calculations as the functions of the same name in the crate root, see their documentation for
+//! details. All dates are in UTC, and all returned times are `OffsetDateTime`s with a UTC offset
+
+use ::time::{Date, OffsetDateTime};
+
+use crate::generic::{self, Crossing};
+use crate::{JulianDate, SunPosition, Twilight, SUNRISE_SUNSET_ANGLE};
+
+/// Calculates the approximate sunset and sunrise times at a given latitude, longitude, and altitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the sunset and sunrise, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in time (outside of the years ±9999), or sunsets/rises cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude)
+///  - `Some((sunrise,sunset))` otherwise
+///
+/// # Examples
+///
+/// ```
+/// use time::{Date, Month};
+///
+/// //Calculate the sunset and sunrise times at Sheffield university's new computer science building
+/// let date = Date::from_calendar_date(2022, Month::June, 1).unwrap();
+/// let (sunrise, sunset) = sun_times::time::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+/// println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+/// ```
+pub fn sun_times(
+    date: Date,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+) -> Option<(OffsetDateTime, OffsetDateTime)> {
+    sun_times_with_angle(date, latitude, longitude, elevation, SUNRISE_SUNSET_ANGLE)
+}
+
+/// Calculates the approximate times of dawn and dusk, for a given kind of twilight, at a given latitude, longitude, and altitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate dawn and dusk, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+/// * `kind` - The kind of twilight to calculate the times for
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in time (outside of the years ±9999), or the sun doesn't cross the twilight's angle on the given day
+///  - `Some((dawn,dusk))` otherwise
+pub fn twilight_times(
+    date: Date,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    kind: Twilight,
+) -> Option<(OffsetDateTime, OffsetDateTime)> {
+    sun_times_with_angle(date, latitude, longitude, elevation, kind.angle())
+}
+
+/// Calculates the approximate times at which the sun's centre crosses a given angle above or below the horizon
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate the times, in UTC
+/// * `latitude` - The latitude at which to calculate the times. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the times. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the times. Expressed as meters above sea level. Negative values will be ignored
+/// * `horizon_angle` - The altitude of the sun's centre at which the times are calculated. Expressed as degrees, negative values being below the horizon
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in time (outside of the years ±9999), or the sun doesn't cross the given angle on the given day
+///  - `Some((rise,set))` otherwise
+pub fn sun_times_with_angle(
+    date: Date,
+    latitude: f64,
+    longitude: f64,
+    elevation: f64,
+    horizon_angle: f64,
+) -> Option<(OffsetDateTime, OffsetDateTime)> {
+    match generic::crossing_within_day(
+        JulianDate::from_time_date(date),
+        latitude,
+        longitude,
+        elevation,
+        horizon_angle,
+    )? {
+        Crossing::At(rise, set) => Some((rise.to_offset_datetime()?, set.to_offset_datetime()?)),
+        Crossing::AlwaysAbove | Crossing::AlwaysBelow => None,
+    }
+}
+
+/// Calculates the approximate time of solar noon (when the sun crosses the meridian, and is at its highest) at a given longitude
+///
+/// # Arguments
+///
+/// * `date` - The date on which to calculate solar noon, in UTC
+/// * `longitude` - The longitude at which to calculate solar noon. Expressed as degrees
+///
+/// # Return value
+///
+/// Returns
+///  - `None` if the date is not representable in time (outside of the years ±9999)
+///  - `Some(solar_noon)` otherwise
+pub fn solar_noon(date: Date, longitude: f64) -> Option<OffsetDateTime> {
+    generic::SunriseEquation::new(JulianDate::from_time_date(date), longitude)
+        .solar_transit()
+        .to_offset_datetime()
+}
+
+/// Calculates the altitude (angle from the horizon) of the sun at a given place and moment, in degrees
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the altitude
+/// * `latitude` - The latitude at which to calculate the altitude. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the altitude. Expressed as degrees
+pub fn altitude(date_time: OffsetDateTime, latitude: f64, longitude: f64) -> f64 {
+    sun_position(date_time, latitude, longitude).altitude
+}
+
+/// Calculates the azimuth (compass bearing) of the sun at a given place and moment, in degrees clockwise from north
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the azimuth
+/// * `latitude` - The latitude at which to calculate the azimuth. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the azimuth. Expressed as degrees
+pub fn azimuth(date_time: OffsetDateTime, latitude: f64, longitude: f64) -> f64 {
+    sun_position(date_time, latitude, longitude).azimuth
+}
+
+/// Calculates both the altitude and azimuth of the sun at a given place and moment
+///
+/// # Arguments
+///
+/// * `date_time` - The date and time on which to calculate the sun's position
+/// * `latitude` - The latitude at which to calculate the sun's position. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sun's position. Expressed as degrees
+pub fn sun_position(date_time: OffsetDateTime, latitude: f64, longitude: f64) -> SunPosition {
+    generic::position(JulianDate::from(date_time), latitude, longitude)
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use ::time::{Date, Month, OffsetDateTime};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    #[test]
+    fn matches_chrono_calculations() {
+        for day in 1..=30 {
+            let date = Date::from_calendar_date(2022, Month::June, day).unwrap();
+            let chrono_date = NaiveDate::from_ymd_opt(2022, 6, day.into()).unwrap();
+
+            let (rise, set) = super::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+            let (chrono_rise, chrono_set) =
+                crate::sun_times(chrono_date, 53.38, -1.48, 100.0).unwrap();
+            assert_eq!(rise.unix_timestamp(), chrono_rise.timestamp());
+            assert_eq!(set.unix_timestamp(), chrono_set.timestamp());
+
+            let noon = super::solar_noon(date, -1.48).unwrap();
+            let chrono_noon = crate::solar_noon(chrono_date, -1.48).unwrap();
+            assert_eq!(noon.unix_timestamp(), chrono_noon.timestamp());
+        }
+
+        let date_time = OffsetDateTime::from_unix_timestamp(1654097420).unwrap();
+        let chrono_date_time = Utc.timestamp_opt(1654097420, 0).unwrap();
+        assert_eq!(
+            super::sun_position(date_time, 53.38, -1.48),
+            crate::sun_position(chrono_date_time, 53.38, -1.48)
+        );
+    }
+
+    #[test]
+    fn nan_is_not_the_unix_epoch() {
+        let date = Date::from_calendar_date(2022, Month::June, 1).unwrap();
+        assert_eq!(super::solar_noon(date, f64::NAN), None);
+        assert_eq!(crate::JulianDate::new(f64::NAN).to_offset_datetime(), None);
+    }
+}
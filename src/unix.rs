@@ -0,0 +1,132 @@
+//! Versions of the calculations over Unix timestamps
+//!
+//! These take and return plain integers (seconds since the Unix epoch, in UTC), so can be passed straight across a
+//! wasm-bindgen boundary to javascript. Like the [julian](crate::julian) module they don't depend on chrono (or std).
+//! Where there's no time to return, e.g. during polar day/night, they return `-1`
+
+use num_traits::Float;
+
+use crate::generic::{self, Crossing};
+use crate::{JulianDate, SECONDS_PER_DAY, SUNRISE_SUNSET_ANGLE, UNIX_EPOCH};
+
+fn to_julian_date(timestamp: i64) -> JulianDate {
+    JulianDate(timestamp as f64 / SECONDS_PER_DAY as f64 + UNIX_EPOCH.0)
+}
+
+fn to_timestamp(julian_date: JulianDate) -> i64 {
+    Float::round((julian_date - UNIX_EPOCH).0 * SECONDS_PER_DAY as f64) as i64
+}
+
+fn crossing(timestamp: i64, latitude: f64, longitude: f64, elevation: f64) -> Option<(i64, i64)> {
+    match generic::crossing_within_day(
+        to_julian_date(timestamp),
+        latitude,
+        longitude,
+        elevation,
+        SUNRISE_SUNSET_ANGLE,
+    )? {
+        Crossing::At(rise, set) => Some((to_timestamp(rise), to_timestamp(set))),
+        Crossing::AlwaysAbove | Crossing::AlwaysBelow => None,
+    }
+}
+
+/// Calculates the approximate sunrise time at a given latitude, longitude, and altitude
+///
+/// This is the same calculation as `sun_times` in the crate root, see its documentation for details
+///
+/// # Arguments
+///
+/// * `timestamp` - A Unix timestamp anywhere within the (UTC) day on which to calculate the sunrise
+/// * `latitude` - The latitude at which to calculate the sunrise. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sunrise. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the sunrise. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `-1` if the sunrise cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude), or NaN inputs
+///  - The sunrise as a Unix timestamp otherwise
+///
+/// # Examples
+///
+/// ```
+/// //Calculate the sunrise time on the 1st of June 2022 at Sheffield university's new computer science building
+/// let sunrise = sun_times::unix::sunrise(1654041600, 53.38, -1.48, 100.0);
+/// println!("Sunrise: {}", sunrise);
+/// ```
+pub fn sunrise(timestamp: i64, latitude: f64, longitude: f64, elevation: f64) -> i64 {
+    crossing(timestamp, latitude, longitude, elevation).map_or(-1, |(rise, _)| rise)
+}
+
+/// Calculates the approximate sunset time at a given latitude, longitude, and altitude
+///
+/// This is the same calculation as `sun_times` in the crate root, see its documentation for details
+///
+/// # Arguments
+///
+/// * `timestamp` - A Unix timestamp anywhere within the (UTC) day on which to calculate the sunset
+/// * `latitude` - The latitude at which to calculate the sunset. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the sunset. Expressed as degrees
+/// * `elevation` - The elevation at which to calculate the sunset. Expressed as meters above sea level. Negative values will be ignored
+///
+/// # Return value
+///
+/// Returns
+///  - `-1` if the sunset cannot be calculated due to long arctic/antarctic day/night (outside ~±67° of latitude), or NaN inputs
+///  - The sunset as a Unix timestamp otherwise
+pub fn sunset(timestamp: i64, latitude: f64, longitude: f64, elevation: f64) -> i64 {
+    crossing(timestamp, latitude, longitude, elevation).map_or(-1, |(_, set)| set)
+}
+
+/// Calculates the approximate time of solar noon at a given longitude, as a Unix timestamp
+///
+/// # Arguments
+///
+/// * `timestamp` - A Unix timestamp anywhere within the (UTC) day on which to calculate solar noon
+/// * `longitude` - The longitude at which to calculate solar noon. Expressed as degrees
+pub fn solar_noon(timestamp: i64, longitude: f64) -> i64 {
+    to_timestamp(
+        generic::SunriseEquation::new(to_julian_date(timestamp), longitude).solar_transit(),
+    )
+}
+
+/// Calculates the altitude (angle from the horizon) of the sun at a given place and moment, in degrees
+///
+/// # Arguments
+///
+/// * `timestamp` - The moment at which to calculate the altitude, as a Unix timestamp
+/// * `latitude` - The latitude at which to calculate the altitude. Expressed as degrees
+/// * `longitude` - The longitude at which to calculate the altitude. Expressed as degrees
+pub fn altitude(timestamp: i64, latitude: f64, longitude: f64) -> f64 {
+    generic::position(to_julian_date(timestamp), latitude, longitude).altitude
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use chrono::NaiveDate;
+
+    #[test]
+    fn matches_chrono_calculations() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 1).unwrap();
+        let midday = date.and_hms_opt(12, 0, 0).unwrap().and_utc().timestamp();
+        let (rise, set) = crate::sun_times(date, 53.38, -1.48, 100.0).unwrap();
+        assert_eq!(
+            super::sunrise(midday, 53.38, -1.48, 100.0),
+            rise.timestamp()
+        );
+        assert_eq!(super::sunset(midday, 53.38, -1.48, 100.0), set.timestamp());
+        assert_eq!(
+            super::solar_noon(midday, -1.48),
+            crate::solar_noon(date, -1.48).unwrap().timestamp()
+        );
+
+        let polar_night = NaiveDate::from_ymd_opt(2022, 12, 21).unwrap();
+        let midday = polar_night
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+        assert_eq!(super::sunrise(midday, 69.65, 18.96, 0.0), -1);
+        assert_eq!(super::sunset(midday, 69.65, 18.96, 0.0), -1);
+    }
+}
@@ -0,0 +1,93 @@
+//! Checks the calculated sunrise and sunset times against a table of reference times
+//!
+//! The reference times in `data/reference_times.csv` were generated with this crate's [sun_times::sun_times_precise],
+//! rather than taken from a published almanac, so they check the simpler calculation against the more precise one
+//! and catch regressions in either, but aren't an independent check of accuracy. The file's header says how. Each row
+//! is the sunrise and sunset falling within the given UTC date, at sea level
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Above this latitude (in degrees) the sun crosses the horizon at a shallow angle, so small errors in its position
+/// make for large errors in the times
+const HIGH_LATITUDE: f64 = 60.0;
+const TOLERANCE_MINUTES: i64 = 2;
+const HIGH_LATITUDE_TOLERANCE_MINUTES: i64 = 10;
+
+struct Reference {
+    city: &'static str,
+    latitude: f64,
+    longitude: f64,
+    date: NaiveDate,
+    sunrise: DateTime<Utc>,
+    sunset: DateTime<Utc>,
+}
+
+fn references() -> Vec<Reference> {
+    include_str!("data/reference_times.csv")
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let [city, latitude, longitude, date, sunrise, sunset] = fields[..] else {
+                panic!("malformed reference row: {}", line);
+            };
+            let time = |time: &str| {
+                DateTime::parse_from_rfc3339(time)
+                    .expect("reference times are RFC 3339")
+                    .with_timezone(&Utc)
+            };
+            Reference {
+                city,
+                latitude: latitude.parse().expect("latitudes are numbers"),
+                longitude: longitude.parse().expect("longitudes are numbers"),
+                date: date.parse().expect("dates are ISO 8601"),
+                sunrise: time(sunrise),
+                sunset: time(sunset),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn sun_times_match_reference_times() {
+    let references = references();
+    assert!(!references.is_empty());
+
+    let mut failures = Vec::new();
+    for reference in &references {
+        let tolerance = if reference.latitude.abs() > HIGH_LATITUDE {
+            HIGH_LATITUDE_TOLERANCE_MINUTES
+        } else {
+            TOLERANCE_MINUTES
+        };
+        match sun_times::sun_times(reference.date, reference.latitude, reference.longitude, 0.0) {
+            Some((sunrise, sunset)) => {
+                for (event, calculated, expected) in [
+                    ("sunrise", sunrise, reference.sunrise),
+                    ("sunset", sunset, reference.sunset),
+                ] {
+                    let error = (calculated - expected).num_seconds().abs();
+                    if error > tolerance * 60 {
+                        failures.push(format!(
+                            "{} {} {}: calculated {}, expected {} ({}s out)",
+                            reference.city, reference.date, event, calculated, expected, error
+                        ));
+                    }
+                }
+            }
+            None => failures.push(format!(
+                "{} {}: no sun times calculated",
+                reference.city, reference.date
+            )),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {} reference days out of tolerance:\n{}",
+        failures.len(),
+        references.len(),
+        failures.join("\n")
+    );
+}